@@ -0,0 +1,51 @@
+//! Benchmark of reading a large contiguous `f64` dataset, run via `cargo bench --bench read`.
+//!
+//! When the in-memory type matches the datatype in the file exactly, HDF5 transfers the
+//! data directly into the output buffer without invoking any type conversions, so reading
+//! should be about as fast as a plain copy of the data in memory. Reading the same values
+//! stored with the opposite byte order (which requires an element-wise conversion) is
+//! measured for comparison.
+
+use std::time::{Duration, Instant};
+
+use hdf5::datatype::ByteOrder;
+use hdf5::{File, Result};
+
+const SIZE: usize = 1 << 24; // 128 MiB of f64 values
+const RUNS: usize = 10;
+
+fn bench<F>(name: &str, data: &[f64], mut func: F) -> Result<()>
+where
+    F: FnMut() -> Result<Vec<f64>>,
+{
+    let mut best = Duration::from_secs(u64::MAX);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let out = func()?;
+        best = best.min(start.elapsed());
+        // also makes sure the result is not optimized away
+        assert!(out.iter().zip(data).all(|(a, b)| a.to_bits() == b.to_bits()));
+    }
+    let throughput = (SIZE * 8) as f64 / best.as_secs_f64() / f64::from(1 << 30);
+    println!("{:<32} {:>12.3?} {:>8.2} GiB/s", name, best, throughput);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let data: Vec<f64> = (0..SIZE).map(|i| (i as f64).sqrt()).collect();
+    let file = File::with_options().with_fapl(|p| p.core()).create("bench_read.h5")?;
+
+    let native = file.new_dataset_builder().with_data(&data).create("native")?;
+    let order =
+        if cfg!(target_endian = "little") { ByteOrder::BigEndian } else { ByteOrder::LittleEndian };
+    let swapped = file.new_dataset::<f64>().byte_order(order).shape(SIZE).create("swapped")?;
+    swapped.write(&data)?;
+
+    bench("copy in memory (baseline)", &data, || Ok(data.clone()))?;
+    bench("read, no-op conversion", &data, || native.read_raw::<f64>())?;
+    bench("read slice, no-op conversion", &data, || {
+        native.read_slice_1d::<f64, _>(..).map(|arr| arr.into_raw_vec())
+    })?;
+    bench("read, byte order conversion", &data, || swapped.read_raw::<f64>())?;
+    Ok(())
+}
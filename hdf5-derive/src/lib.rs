@@ -8,10 +8,18 @@ use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_error::{abort, proc_macro_error};
 use quote::{quote, ToTokens};
 use syn::{
-    parse_macro_input, AttrStyle, Attribute, Data, DeriveInput, Expr, Fields, Index, Lit, Meta,
-    NestedMeta, Type, TypeGenerics, TypePath,
+    parse_macro_input, AttrStyle, Attribute, Data, DeriveInput, Expr, Field, Fields, Index, Lit,
+    Meta, NestedMeta, Type, TypeGenerics, TypePath,
 };
 
+/// Derives `H5Type` for structs (stored as compound types), enums with an explicit integer
+/// representation (stored as enum types) and `#[repr(C)]` unions.
+///
+/// Unions are stored as fixed-size arrays of bytes holding the raw memory of the union,
+/// since HDF5 has no union types and compound members can't overlap. The bytes are neither
+/// converted nor byte-swapped by the library, so the data is only meaningful to readers with
+/// the same memory layout (e.g. files written on little-endian machines are not portable to
+/// big-endian ones), and other tools will only see the array of bytes.
 #[proc_macro_derive(H5Type, attributes(hdf5))]
 #[proc_macro_error]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -67,6 +75,18 @@ fn impl_transparent(ty: &Type) -> TokenStream {
     }
 }
 
+fn impl_union(ty: &Ident, ty_generics: &TypeGenerics) -> TokenStream {
+    // HDF5 compound types can't have overlapping members, so unions are stored as raw bytes
+    // (in native byte order, see the docs of the derive macro)
+    quote! {
+        let size = ::std::mem::size_of::<#ty #ty_generics>();
+        _h5::types::TypeDescriptor::FixedArray(
+            ::std::boxed::Box::new(_h5::types::TypeDescriptor::Unsigned(_h5::types::IntSize::U1)),
+            size,
+        )
+    }
+}
+
 fn impl_enum(names: &[String], values: &[Expr], repr: &Ident) -> TokenStream {
     let size = Ident::new(
         &format!(
@@ -154,6 +174,22 @@ fn find_hdf5_rename(attrs: &[Attribute]) -> Option<String> {
     None
 }
 
+fn is_hdf5_skipped(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|a| a.path.is_ident("hdf5")).any(|attr| match attr.parse_meta() {
+        Ok(Meta::List(meta_list)) => meta_list
+            .nested
+            .iter()
+            .any(|n| matches!(n, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip"))),
+        _ => false,
+    })
+}
+
+/// Returns true if the field is not a part of the compound type; the memory it occupies
+/// is then treated as padding.
+fn is_excluded(field: &Field) -> bool {
+    is_phantom_data(&field.ty) || is_hdf5_skipped(&field.attrs)
+}
+
 fn pluck<'a, I, F, T, S>(iter: I, func: F) -> Vec<S>
 where
     I: Iterator<Item = &'a T>,
@@ -172,8 +208,7 @@ fn impl_trait(
                 abort!(ty, "cannot derive `H5Type` for unit structs");
             }
             Fields::Named(ref fields) => {
-                let fields: Vec<_> =
-                    fields.named.iter().filter(|f| !is_phantom_data(&f.ty)).collect();
+                let fields: Vec<_> = fields.named.iter().filter(|f| !is_excluded(f)).collect();
                 if fields.is_empty() {
                     abort!(ty, "cannot derive `H5Type` for empty structs");
                 }
@@ -201,7 +236,7 @@ fn impl_trait(
                     .unnamed
                     .iter()
                     .enumerate()
-                    .filter(|&(_, f)| !is_phantom_data(&f.ty))
+                    .filter(|&(_, f)| !is_excluded(f))
                     .map(|(i, f)| (Index::from(i), f))
                     .unzip();
                 if fields.is_empty() {
@@ -250,7 +285,10 @@ fn impl_trait(
             impl_enum(&names, &values, &repr)
         }
         Data::Union(_) => {
-            abort!(ty, "cannot derive `H5Type` for tagged unions");
+            find_repr(attrs, &["C"]).unwrap_or_else(|| {
+                abort!(ty, "`H5Type` requires repr(C) for unions");
+            });
+            impl_union(ty, ty_generics)
         }
     }
 }
@@ -0,0 +1,12 @@
+extern crate hdf5_derive;
+use hdf5_derive::H5Type;
+
+#[derive(H5Type)]
+//~^ ERROR proc-macro derive
+//~^^ HELP H5Type requires repr(C) for unions
+union Foo {
+    bar: i64,
+    baz: f64,
+}
+
+fn main() {}
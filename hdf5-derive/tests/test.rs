@@ -254,3 +254,71 @@ fn test_phantom_data() {
     assert_eq!(G3::<String>::type_descriptor(), C3::type_descriptor());
     assert_eq!(G4::<String>::type_descriptor(), C4::type_descriptor());
 }
+
+#[derive(H5Type)]
+#[repr(C)]
+struct S1 {
+    x: i16,
+    #[hdf5(skip)]
+    cache: Option<Vec<u8>>,
+    z: u32,
+}
+
+#[derive(H5Type)]
+#[repr(C)]
+struct S2(i8, #[hdf5(skip)] f64, #[hdf5(rename = "z")] u16);
+
+#[test]
+fn test_skip() {
+    assert_eq!(
+        S1::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<i16>("x", 0, 0),
+                CompoundField::typed::<u32>("z", 8 + mem::size_of::<Option<Vec<u8>>>(), 1),
+            ],
+            size: mem::size_of::<S1>(),
+        })
+    );
+    assert_eq!(
+        S2::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<i8>("0", 0, 0),
+                CompoundField::typed::<u16>("z", 16, 1),
+            ],
+            size: 24,
+        })
+    );
+}
+
+#[derive(H5Type, Clone, Copy)]
+#[repr(C)]
+union U1 {
+    x: u8,
+    y: [u16; 3],
+    z: f32,
+}
+
+#[derive(H5Type, Clone, Copy)]
+#[repr(C)]
+struct TaggedU1 {
+    tag: u8,
+    value: U1,
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(mem::size_of::<U1>(), 8);
+    assert_eq!(U1::type_descriptor(), TD::FixedArray(Box::new(TD::Unsigned(IntSize::U1)), 8));
+    assert_eq!(
+        TaggedU1::type_descriptor(),
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u8>("tag", 0, 0),
+                CompoundField::typed::<U1>("value", 4, 1),
+            ],
+            size: 12,
+        })
+    );
+}
@@ -71,7 +71,7 @@ pub struct H5L_info2_t {
     pub corder_valid: hbool_t,
     pub corder: int64_t,
     pub cset: H5T_cset_t,
-    pub u: H5L_info1_t__u,
+    pub u: H5L_info2_t__u,
 }
 
 #[cfg(feature = "1.12.0")]
@@ -96,6 +96,23 @@ impl Default for H5L_info2_t__u {
     }
 }
 
+#[cfg(feature = "1.12.0")]
+impl H5L_info2_t__u {
+    pub unsafe fn token(&mut self) -> *mut H5O_token_t {
+        &mut self.token
+    }
+    pub unsafe fn val_size(&mut self) -> *mut size_t {
+        &mut self.val_size
+    }
+}
+
+#[cfg(feature = "1.12.0")]
+impl std::fmt::Debug for H5L_info2_t__u {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("H5L_info2_t__u { .. }")
+    }
+}
+
 pub type H5L_create_func_t = Option<
     extern "C" fn(
         link_name: *const c_char,
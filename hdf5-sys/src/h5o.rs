@@ -365,7 +365,7 @@ extern "C" {
 pub const H5O_MAX_TOKEN_SIZE: usize = 16;
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg(feature = "1.12.0")]
 pub struct H5O_token_t {
     __data: [u8; H5O_MAX_TOKEN_SIZE],
@@ -105,6 +105,25 @@ pub struct CompoundType {
 }
 
 impl CompoundType {
+    /// Returns the gaps between the fields (and after the last field) that are not occupied
+    /// by any of them, as `(offset, size)` pairs in bytes, sorted by offset.
+    pub fn padding(&self) -> Vec<(usize, usize)> {
+        let mut fields: Vec<_> = self.fields.iter().collect();
+        fields.sort_by_key(|f| f.offset);
+        let mut padding = Vec::new();
+        let mut offset = 0;
+        for f in fields {
+            if f.offset > offset {
+                padding.push((offset, f.offset - offset));
+            }
+            offset = offset.max(f.offset + f.ty.size());
+        }
+        if self.size > offset {
+            padding.push((offset, self.size - offset));
+        }
+        padding
+    }
+
     pub fn to_c_repr(&self) -> Self {
         let mut layout = self.clone();
         layout.fields.sort_by_key(|f| f.index);
@@ -475,6 +494,28 @@ pub mod tests {
         assert_eq!(mem::size_of::<T2>(), 16);
     }
 
+    #[test]
+    pub fn test_compound_padding() {
+        let td = CompoundType {
+            fields: vec![
+                CompoundField::typed::<u32>("b", 4, 1),
+                CompoundField::typed::<u8>("a", 0, 0),
+                CompoundField::typed::<u16>("c", 8, 2),
+            ],
+            size: 12,
+        };
+        assert_eq!(td.padding(), vec![(1, 3), (10, 2)]);
+        assert!(td.to_packed_repr().padding().is_empty());
+
+        type T = (i8, u64, f32, bool);
+        let td = match T::type_descriptor() {
+            TD::Compound(td) => td,
+            _ => unreachable!(),
+        };
+        assert_eq!(td.padding(), vec![(14, 2)]);
+        assert_eq!(td.to_c_repr().padding(), vec![(1, 7), (21, 3)]);
+    }
+
     #[test]
     pub fn test_tuple_various_reprs() {
         type T = (i8, u64, f32, bool);
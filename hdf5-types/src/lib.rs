@@ -12,6 +12,12 @@
 //!                     in different libraries (e.g. dynamic libraries on windows),
 //!                     or if `hdf5-c` is compiled with the MEMCHECKER option.
 //!                     This option is forced on in the case of using a `windows` DLL.
+//! * `std-types`: Add `Ipv4Octets`, `Ipv6Octets` (`u8` arrays of address octets) and
+//!                     `RawDuration` (a compound of `secs: u64` and `nanos: u32`),
+//!                     convertible to and from `std::net::{Ipv4Addr, Ipv6Addr}` and
+//!                     `std::time::Duration`.
+//! * `chrono`: Add `Timestamp`, a UTC timestamp stored as `i64` nanoseconds since the Unix
+//!                     epoch, convertible to and from `chrono::DateTime<Utc>`.
 
 #[cfg(test)]
 #[macro_use]
@@ -20,14 +26,22 @@ extern crate quickcheck;
 mod array;
 pub mod dyn_value;
 mod h5type;
+#[cfg(feature = "std-types")]
+mod std_types;
 mod string;
+#[cfg(feature = "chrono")]
+mod timestamp;
 
 pub use self::array::VarLenArray;
 pub use self::dyn_value::{DynValue, OwnedDynValue};
 pub use self::h5type::{
     CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
 };
+#[cfg(feature = "std-types")]
+pub use self::std_types::{DurationRangeError, Ipv4Octets, Ipv6Octets, RawDuration};
 pub use self::string::{FixedAscii, FixedUnicode, StringError, VarLenAscii, VarLenUnicode};
+#[cfg(feature = "chrono")]
+pub use self::timestamp::{Timestamp, TimestampRangeError};
 
 pub(crate) unsafe fn malloc(n: usize) -> *mut core::ffi::c_void {
     cfg_if::cfg_if! {
@@ -0,0 +1,170 @@
+//! Storable counterparts of some of the standard library types.
+//!
+//! The in-memory layout of `Ipv4Addr`, `Ipv6Addr` and `Duration` is not guaranteed by the
+//! standard library (and `Duration` can't hold arbitrary bit patterns, since its nanoseconds
+//! must be below 10<sup>9</sup>), so they can't implement `H5Type` directly; instead, they
+//! are converted to and from `#[repr(C)]` wrapper types with a fixed layout:
+//! * [`Ipv4Octets`]: `[u8; 4]` (address octets in network order).
+//! * [`Ipv6Octets`]: `[u8; 16]` (address octets in network order).
+//! * [`RawDuration`]: compound `{secs: u64, nanos: u32}`; converting it to `Duration` fails
+//!   if the stored nanoseconds are out of range.
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+use crate::h5type::{CompoundField, CompoundType, H5Type, TypeDescriptor};
+
+/// An IPv4 address stored as 4 octets in network order.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4Octets(pub [u8; 4]);
+
+unsafe impl H5Type for Ipv4Octets {
+    fn type_descriptor() -> TypeDescriptor {
+        <[u8; 4]>::type_descriptor()
+    }
+}
+
+impl From<Ipv4Addr> for Ipv4Octets {
+    fn from(addr: Ipv4Addr) -> Self {
+        Self(addr.octets())
+    }
+}
+
+impl From<Ipv4Octets> for Ipv4Addr {
+    fn from(octets: Ipv4Octets) -> Self {
+        octets.0.into()
+    }
+}
+
+/// An IPv6 address stored as 16 octets in network order.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv6Octets(pub [u8; 16]);
+
+unsafe impl H5Type for Ipv6Octets {
+    fn type_descriptor() -> TypeDescriptor {
+        <[u8; 16]>::type_descriptor()
+    }
+}
+
+impl From<Ipv6Addr> for Ipv6Octets {
+    fn from(addr: Ipv6Addr) -> Self {
+        Self(addr.octets())
+    }
+}
+
+impl From<Ipv6Octets> for Ipv6Addr {
+    fn from(octets: Ipv6Octets) -> Self {
+        octets.0.into()
+    }
+}
+
+/// A duration stored as whole seconds and nanoseconds (like `std::time::Duration`).
+///
+/// Unlike `Duration`, any values can be read into it; converting it via
+/// `Duration::try_from()` fails if `nanos` is not below 10<sup>9</sup>.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawDuration {
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+unsafe impl H5Type for RawDuration {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u64>("secs", 0, 0),
+                CompoundField::typed::<u32>("nanos", mem::size_of::<u64>(), 1),
+            ],
+            size: mem::size_of::<Self>(),
+        })
+    }
+}
+
+impl From<Duration> for RawDuration {
+    fn from(duration: Duration) -> Self {
+        Self { secs: duration.as_secs(), nanos: duration.subsec_nanos() }
+    }
+}
+
+impl TryFrom<RawDuration> for Duration {
+    type Error = DurationRangeError;
+
+    fn try_from(raw: RawDuration) -> Result<Self, Self::Error> {
+        if raw.nanos < 1_000_000_000 {
+            Ok(Self::new(raw.secs, raw.nanos))
+        } else {
+            Err(DurationRangeError)
+        }
+    }
+}
+
+/// The error returned when converting a [`RawDuration`] with out-of-range nanoseconds
+/// to a `Duration`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationRangeError;
+
+impl Display for DurationRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("nanoseconds of a duration must be less than 1_000_000_000")
+    }
+}
+
+impl std::error::Error for DurationRangeError {}
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+    use std::mem;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
+
+    use super::{DurationRangeError, Ipv4Octets, Ipv6Octets, RawDuration};
+    use crate::h5type::{H5Type, IntSize, TypeDescriptor as TD};
+
+    #[test]
+    pub fn test_ip_addrs() {
+        assert_eq!(
+            Ipv4Octets::type_descriptor(),
+            TD::FixedArray(Box::new(TD::Unsigned(IntSize::U1)), 4)
+        );
+        assert_eq!(
+            Ipv6Octets::type_descriptor(),
+            TD::FixedArray(Box::new(TD::Unsigned(IntSize::U1)), 16)
+        );
+        let v4 = Ipv4Addr::new(192, 168, 0, 1);
+        assert_eq!(Ipv4Octets::from(v4).0, [192, 168, 0, 1]);
+        assert_eq!(Ipv4Addr::from(Ipv4Octets::from(v4)), v4);
+        let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x42);
+        assert_eq!(Ipv6Octets::from(v6).0, v6.octets());
+        assert_eq!(Ipv6Addr::from(Ipv6Octets::from(v6)), v6);
+    }
+
+    #[test]
+    pub fn test_duration() {
+        let tp = match RawDuration::type_descriptor() {
+            TD::Compound(tp) => tp,
+            _ => panic!("expected a compound type"),
+        };
+        assert_eq!(tp.size, mem::size_of::<RawDuration>());
+        let names: Vec<_> = tp.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["secs", "nanos"]);
+        assert_eq!(
+            (&tp.fields[0].ty, &tp.fields[1].ty),
+            (&TD::Unsigned(IntSize::U8), &TD::Unsigned(IntSize::U4))
+        );
+        assert_eq!(tp.padding().iter().map(|&(_, size)| size).sum::<usize>(), 4);
+
+        let duration = Duration::new(1_234_567_890_123, 999_999_999);
+        let raw = RawDuration::from(duration);
+        assert_eq!(raw, RawDuration { secs: 1_234_567_890_123, nanos: 999_999_999 });
+        assert_eq!(Duration::try_from(raw), Ok(duration));
+        let raw = RawDuration { secs: 1, nanos: 1_000_000_000 };
+        assert_eq!(Duration::try_from(raw), Err(DurationRangeError));
+    }
+}
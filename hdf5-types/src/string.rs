@@ -443,6 +443,14 @@ impl<const N: usize> FixedAscii<N> {
     }
 }
 
+impl<const N: usize> FromStr for FixedAscii<N> {
+    type Err = StringError;
+
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        Self::from_ascii(s)
+    }
+}
+
 impl<const N: usize> AsAsciiStr for FixedAscii<N> {
     type Inner = u8;
 
@@ -633,6 +641,24 @@ pub mod tests {
         assert!(U::from_str("€").is_err());
     }
 
+    #[test]
+    pub fn test_fixed_from_str() {
+        type A = FixedAscii<16>;
+        type U = FixedUnicode<16>;
+        let a: A = "STATION-01".parse().unwrap();
+        assert_eq!(a.as_str(), "STATION-01");
+        assert_eq!(a.len(), 10);
+        assert_eq!(a.to_string(), "STATION-01");
+        assert_eq!(A::from_str(a.as_str()).unwrap(), a);
+        assert_eq!("0123456789abcdefg".parse::<A>(), Err(StringError::InsufficientCapacity));
+        assert!(matches!("STATION-®".parse::<A>(), Err(StringError::AsciiError(_))));
+        let u: U = "station-®".parse().unwrap();
+        assert_eq!(u.as_str(), "station-®");
+        assert_eq!(u.to_string(), "station-®");
+        assert_eq!(U::from_str(u.as_str()).unwrap(), u);
+        assert_eq!("€€€€€€".parse::<U>(), Err(StringError::InsufficientCapacity));
+    }
+
     #[test]
     pub fn test_non_ascii() {
         assert!(VA::from_ascii("®").is_err());
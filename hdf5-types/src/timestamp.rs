@@ -0,0 +1,127 @@
+//! UTC timestamps stored as `i64` nanoseconds since the Unix epoch.
+//!
+//! `chrono::DateTime<Utc>` has no stable in-memory representation, so it can't implement
+//! `H5Type` directly; instead, it is converted to and from [`Timestamp`], which is stored as
+//! a native 64-bit signed integer. This is also how `pandas` and `numpy` (`datetime64[ns]`)
+//! represent timestamps, so the data can be loaded by them without any further conversions.
+//!
+//! By convention, datasets of timestamps should have a string attribute `units` set to
+//! [`Timestamp::UNITS`] (`"nanoseconds since 1970-01-01T00:00:00Z"`), so that the readers
+//! don't have to guess the meaning of the stored integers.
+//!
+//! Note that the HDF5 `H5T_TIME` datatype class is not used since it's not supported by
+//! the HDF5 library itself (no conversions to or from it are defined).
+
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::h5type::{H5Type, TypeDescriptor};
+
+/// A UTC timestamp stored as nanoseconds since the Unix epoch (`1970-01-01T00:00:00Z`).
+///
+/// The representable range is approximately from year 1677 to year 2262.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// The recommended value of the `units` attribute of datasets containing timestamps.
+    pub const UNITS: &'static str = "nanoseconds since 1970-01-01T00:00:00Z";
+
+    /// Creates a timestamp from the number of nanoseconds since the Unix epoch.
+    pub const fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    /// Returns the number of nanoseconds since the Unix epoch.
+    pub const fn nanos(self) -> i64 {
+        self.0
+    }
+
+    /// Converts a `DateTime<Utc>`, returning `None` if it is out of the representable range.
+    pub fn from_datetime(datetime: &DateTime<Utc>) -> Option<Self> {
+        datetime.timestamp_nanos_opt().map(Self)
+    }
+
+    /// Converts the timestamp to `DateTime<Utc>` (this conversion is lossless).
+    pub fn to_datetime(self) -> DateTime<Utc> {
+        Utc.timestamp_nanos(self.0)
+    }
+}
+
+unsafe impl H5Type for Timestamp {
+    fn type_descriptor() -> TypeDescriptor {
+        <i64 as H5Type>::type_descriptor()
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_datetime()
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for Timestamp {
+    type Error = TimestampRangeError;
+
+    fn try_from(datetime: DateTime<Utc>) -> Result<Self, Self::Error> {
+        Self::from_datetime(&datetime).ok_or(TimestampRangeError)
+    }
+}
+
+impl Debug for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Timestamp({:?})", self.to_datetime())
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.to_datetime(), f)
+    }
+}
+
+/// The error returned when converting an out-of-range `DateTime<Utc>` to a [`Timestamp`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampRangeError;
+
+impl Display for TimestampRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("datetime is out of range for a nanosecond timestamp")
+    }
+}
+
+impl std::error::Error for TimestampRangeError {}
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{Timestamp, TimestampRangeError};
+    use crate::h5type::{H5Type, IntSize, TypeDescriptor as TD};
+
+    #[test]
+    pub fn test_timestamp() {
+        assert_eq!(Timestamp::type_descriptor(), TD::Integer(IntSize::U8));
+
+        let datetime = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap()
+            + chrono::Duration::nanoseconds(123_456_789);
+        let timestamp = Timestamp::try_from(datetime).unwrap();
+        assert_eq!(timestamp.nanos(), 1_614_834_367_123_456_789);
+        assert_eq!(DateTime::<Utc>::from(timestamp), datetime);
+
+        let before_epoch = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(Timestamp::from_datetime(&before_epoch).unwrap().nanos(), -1_000_000_000);
+
+        let far_future = Utc.with_ymd_and_hms(3000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(Timestamp::try_from(far_future), Err(TimestampRangeError));
+        for &nanos in &[i64::MIN, -1, 0, 1, i64::MAX] {
+            let timestamp = Timestamp::from_nanos(nanos);
+            assert_eq!(Timestamp::try_from(timestamp.to_datetime()), Ok(timestamp));
+        }
+    }
+}
@@ -81,6 +81,19 @@ pub trait ObjectClass: Sized {
     }
 }
 
+/// Wraps a raw HDF5 identifier (e.g. one obtained from C code using HDF5) into an object.
+///
+/// The wrapper takes ownership of the identifier, i.e. of one reference to it: the reference
+/// count is decremented when the wrapper is dropped, closing the object if it was the last
+/// reference. If the caller needs to keep using the identifier as well, increment its
+/// reference count beforehand (`H5Iinc_ref`). Borrowed identifiers can be retrieved back via
+/// `Object::id()`.
+///
+/// # Safety
+///
+/// The caller must own the reference being transferred and must not close the identifier
+/// through it afterwards; otherwise an unrelated object that reuses the identifier may get
+/// closed when the wrapper is dropped.
 pub unsafe fn from_id<T: ObjectClass>(id: hid_t) -> Result<T> {
     T::from_id(id)
 }
@@ -15,6 +15,12 @@ use hdf5_sys::h5e::{
     H5E_DEFAULT, H5E_WALK_DOWNWARD,
 };
 
+#[cfg(feature = "1.12.1")]
+use crate::globals::H5E_CANTLOCKFILE;
+use crate::globals::{
+    H5E_CANTCREATE, H5E_CANTLOCK, H5E_CANTOPENFILE, H5E_FILE, H5E_FILEEXISTS, H5E_NOTFOUND,
+    H5E_NOTHDF5, H5E_TRUNCATED, H5E_VERSION,
+};
 use crate::internal_prelude::*;
 
 /// Silence errors emitted by `hdf5`
@@ -80,7 +86,9 @@ impl ErrorStack {
                     let (desc, func) = (string_from_cstr(e.desc), string_from_cstr(e.func_name));
                     let major = get_h5_str(|m, s| H5Eget_msg(e.maj_num, ptr::null_mut(), m, s))?;
                     let minor = get_h5_str(|m, s| H5Eget_msg(e.min_num, ptr::null_mut(), m, s))?;
-                    Ok(ErrorFrame::new(&desc, &func, &major, &minor))
+                    let mut frame = ErrorFrame::new(&desc, &func, &major, &minor);
+                    frame.codes = (e.maj_num, e.min_num);
+                    Ok(frame)
                 };
                 match closure(*err_desc) {
                     Ok(frame) => {
@@ -114,6 +122,7 @@ pub struct ErrorFrame {
     major: String,
     minor: String,
     description: String,
+    codes: (hid_t, hid_t),
 }
 
 impl ErrorFrame {
@@ -124,9 +133,15 @@ impl ErrorFrame {
             major: major.into(),
             minor: minor.into(),
             description: format!("{}(): {}", func, desc),
+            codes: (H5I_INVALID_HID, H5I_INVALID_HID),
         }
     }
 
+    /// Returns the major and minor error codes (message identifiers) of this frame.
+    pub fn codes(&self) -> (hid_t, hid_t) {
+        self.codes
+    }
+
     pub fn desc(&self) -> &str {
         self.desc.as_ref()
     }
@@ -192,6 +207,61 @@ impl ExpandedErrorStack {
     pub fn detail(&self) -> Option<String> {
         self.top().and_then(ErrorFrame::detail)
     }
+
+    /// Attempts to classify the cause of a failure to open or create a file.
+    ///
+    /// Returns `None` if the stack doesn't contain any file-related errors.
+    pub fn open_error(&self) -> Option<OpenError> {
+        let has_minor = |code: hid_t| self.iter().any(|frame| frame.codes.1 == code);
+        // missing and (before 1.12.1) locked files are detected when opening the file and
+        // reported as file errors with these generic codes, see `FileBuilder::open_as()`
+        let has_file_error =
+            |code: hid_t| self.iter().any(|frame| frame.codes == (*H5E_FILE, code));
+        #[cfg(feature = "1.12.1")]
+        let locked = has_minor(*H5E_CANTLOCKFILE);
+        #[cfg(not(feature = "1.12.1"))]
+        let locked = false;
+        let locked = locked || has_file_error(*H5E_CANTLOCK);
+        if has_file_error(*H5E_NOTFOUND) {
+            Some(OpenError::NotFound)
+        } else if has_minor(*H5E_NOTHDF5) {
+            Some(OpenError::BadSignature)
+        } else if has_minor(*H5E_VERSION) {
+            Some(OpenError::BadVersion)
+        } else if has_minor(*H5E_TRUNCATED) {
+            Some(OpenError::Truncated)
+        } else if locked {
+            Some(OpenError::Locked)
+        } else if has_minor(*H5E_FILEEXISTS) {
+            Some(OpenError::AlreadyExists)
+        } else if has_minor(*H5E_CANTOPENFILE) || has_minor(*H5E_CANTCREATE) {
+            Some(OpenError::CantOpen)
+        } else {
+            None
+        }
+    }
+}
+
+/// Causes of failures when opening or creating files, see
+/// [`Error::open_error`](Error::open_error).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OpenError {
+    /// The file could not be opened or created by the file driver; e.g., there are not
+    /// enough permissions, or it has already been opened in a conflicting mode.
+    CantOpen,
+    /// The file to be opened doesn't exist.
+    NotFound,
+    /// File signature not found; most likely it's not an HDF5 file.
+    BadSignature,
+    /// The superblock (or some other file structure) version is not supported.
+    BadVersion,
+    /// The file is truncated (its size is smaller than expected).
+    Truncated,
+    /// The file could not be locked, likely because it's used by another process.
+    Locked,
+    /// The file already exists (exclusive creation mode).
+    AlreadyExists,
 }
 
 /// The error type for HDF5-related functions.
@@ -217,6 +287,18 @@ impl Error {
             Err(Self::Internal("Could not get errorstack".to_owned()))
         }
     }
+
+    /// If this error was caused by a failure to open or create a file, attempts to
+    /// determine the cause from the HDF5 error stack.
+    ///
+    /// This allows, for instance, retrying opening a file that is locked by another
+    /// process while failing fast if the file is corrupt.
+    pub fn open_error(&self) -> Option<OpenError> {
+        match self {
+            Self::HDF5(stack) => stack.clone().expand().ok()?.open_error(),
+            Self::Internal(_) => None,
+        }
+    }
 }
 
 impl From<&str> for Error {
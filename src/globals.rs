@@ -320,6 +320,8 @@ link_hid!(H5E_CANTMODIFY, h5e::H5E_CANTMODIFY);
 link_hid!(H5E_CANTREMOVE, h5e::H5E_CANTREMOVE);
 link_hid!(H5E_CANTCONVERT, h5e::H5E_CANTCONVERT);
 link_hid!(H5E_BADSIZE, h5e::H5E_BADSIZE);
+#[cfg(feature = "1.12.1")]
+link_hid!(H5E_CANTLOCKFILE, h5e::H5E_CANTLOCKFILE);
 
 // H5R constants
 lazy_static! {
@@ -7,25 +7,30 @@ pub mod extents;
 pub mod file;
 pub mod filters;
 pub mod group;
+pub mod lazy;
 pub mod location;
 pub mod object;
 pub mod plist;
 pub mod selection;
+pub mod table;
 
 pub use self::{
     attribute::{
         Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
         AttributeBuilderEmptyShape,
     },
-    container::{ByteReader, Container, Reader, Writer},
+    container::{AnyArray, ByteReader, Container, Reader, Writer},
     dataset::{
-        Dataset, DatasetBuilder, DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape,
+        ByteWriter, Dataset, DatasetBuilder, DatasetBuilderData, DatasetBuilderEmpty,
+        DatasetBuilderEmptyShape,
     },
     dataspace::Dataspace,
     datatype::{Conversion, Datatype},
-    file::{File, FileBuilder, OpenMode},
-    group::{Group, LinkInfo, LinkType},
+    file::{File, FileBuilder, FileIntent, OpenMode},
+    group::{Group, GroupBuilder, IterAction, LinkInfo, LinkTarget, LinkType},
+    lazy::LazyDataset,
     location::{Location, LocationInfo, LocationToken, LocationType},
     object::Object,
     plist::PropertyList,
+    table::Table,
 };
@@ -75,6 +75,63 @@ impl Attribute {
 
         Ok(result)
     }
+
+    /// Visits the names of all attributes attached to the object in increasing name order,
+    /// passing the index of each attribute along with its name.
+    ///
+    /// Returning [`IterAction::Stop`] from the closure stops the iteration early.
+    pub fn for_each_attr<F>(obj: &Location, mut op: F) -> Result<()>
+    where
+        F: FnMut(usize, &str) -> IterAction,
+    {
+        struct Vtable<'a, F> {
+            f: &'a mut F,
+            index: usize,
+        }
+
+        extern "C" fn attributes_callback<F>(
+            _id: hid_t, attr_name: *const c_char, _info: *const H5A_info_t, op_data: *mut c_void,
+        ) -> herr_t
+        where
+            F: FnMut(usize, &str) -> IterAction,
+        {
+            std::panic::catch_unwind(|| {
+                let vtable = op_data.cast::<Vtable<F>>();
+                let vtable = unsafe { vtable.as_mut().expect("for_each_attr: null op_data ptr") };
+                let name = string_from_cstr(attr_name);
+                let index = vtable.index;
+                vtable.index += 1;
+                match (vtable.f)(index, &name) {
+                    IterAction::Continue => 0,
+                    IterAction::Stop => 1,
+                }
+            })
+            .unwrap_or(-1)
+        }
+
+        let callback_fn: H5A_operator2_t = Some(attributes_callback::<F>);
+        let iteration_position: *mut hsize_t = &mut { 0_u64 };
+        let mut vtable = Vtable { f: &mut op, index: 0 };
+        let other_data = (&mut vtable as *mut Vtable<_>).cast::<c_void>();
+
+        h5call!(H5Aiterate2(
+            obj.handle().id(),
+            H5_index_t::H5_INDEX_NAME,
+            H5_iter_order_t::H5_ITER_INC,
+            iteration_position,
+            callback_fn,
+            other_data
+        ))
+        .and(Ok(()))
+    }
+
+    /// Reads a 1-D attribute (e.g. `valid_range`) into a vector.
+    ///
+    /// Unlike `read_raw()`, which flattens the data of any shape, this fails if the attribute
+    /// is not one-dimensional.
+    pub fn read_vec<T: H5Type>(&self) -> Result<Vec<T>> {
+        self.read_1d().map(|arr| arr.into_raw_vec())
+    }
 }
 
 #[derive(Clone)]
@@ -204,10 +261,6 @@ where
     }
 
     pub fn create<'n, N: Into<&'n str>>(&self, name: N) -> Result<Attribute> {
-        ensure!(
-            self.data.is_standard_layout(),
-            "input array is not in standard layout or is not contiguous"
-        ); // TODO: relax this when it's supported in the writer
         let extents = Extents::from(self.data.shape());
         let name = name.into();
 
@@ -284,7 +337,7 @@ impl AttributeBuilderInner {
 #[cfg(test)]
 pub mod attribute_tests {
     use crate::internal_prelude::*;
-    use ndarray::{arr2, Array2};
+    use ndarray::{arr1, arr2, Array2};
     use std::str::FromStr;
     use types::VarLenUnicode;
 
@@ -343,6 +396,40 @@ pub mod attribute_tests {
         })
     }
 
+    #[test]
+    pub fn test_type_class() {
+        use crate::datatype::TypeClass;
+
+        with_tmp_file(|file| {
+            let attr = file.new_attr::<i16>().shape(()).create("scalar").unwrap();
+            assert!(attr.is_scalar());
+            assert_eq!(attr.ndim(), 0);
+            assert_eq!(
+                attr.dtype().unwrap().type_class().unwrap(),
+                TypeClass::Integer { signed: true, size: 2 }
+            );
+
+            let attr = file.new_attr_builder().with_data(&[1.5_f64, 2.5]).create("vec").unwrap();
+            assert!(!attr.is_scalar());
+            assert_eq!((attr.shape(), attr.ndim()), (vec![2], 1));
+            assert_eq!(attr.dtype().unwrap().type_class().unwrap(), TypeClass::Float { size: 8 });
+
+            let s = VarLenUnicode::from_str("foo").unwrap();
+            let attr = file.new_attr::<VarLenUnicode>().shape(3).create("str").unwrap();
+            attr.write_raw(&[s.clone(), s.clone(), s]).unwrap();
+            assert_eq!(attr.shape(), vec![3]);
+            assert_eq!(
+                attr.dtype().unwrap().type_class().unwrap(),
+                TypeClass::String { variable: true, is_utf8: true }
+            );
+            let attr = file.new_attr::<types::FixedAscii<5>>().shape(()).create("fixed").unwrap();
+            assert_eq!(
+                attr.dtype().unwrap().type_class().unwrap(),
+                TypeClass::String { variable: false, is_utf8: false }
+            );
+        })
+    }
+
     #[test]
     pub fn test_read_write() {
         with_tmp_file(|file| {
@@ -360,6 +447,27 @@ pub mod attribute_tests {
         })
     }
 
+    #[test]
+    pub fn test_read_vec() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i16>().shape(10).create("x").unwrap();
+            ds.new_attr_builder().with_data(&arr1(&[-1.5, 99.5])).create("valid_range").unwrap();
+            ds.new_attr_builder().with_data(&arr2(&[[1, 2], [3, 4]])).create("matrix").unwrap();
+
+            let attr = ds.attr("valid_range").unwrap();
+            assert_eq!(attr.read_vec::<f64>().unwrap(), vec![-1.5, 99.5]);
+            assert_eq!(ds.attr_vec::<f64>("valid_range").unwrap(), vec![-1.5, 99.5]);
+            assert_eq!(attr.read_raw::<f64>().unwrap(), vec![-1.5, 99.5]);
+
+            // `read_raw()` flattens the data, `read_vec()` requires a 1-D attribute
+            let attr = ds.attr("matrix").unwrap();
+            assert_eq!(attr.read_raw::<i32>().unwrap(), vec![1, 2, 3, 4]);
+            assert_err!(attr.read_vec::<i32>(), "ndim mismatch");
+            assert_err!(ds.attr_vec::<i32>("matrix"), "ndim mismatch");
+            assert!(ds.attr_vec::<i32>("missing").is_err());
+        })
+    }
+
     #[test]
     pub fn test_create() {
         with_tmp_file(|file| {
@@ -415,6 +523,42 @@ pub mod attribute_tests {
         })
     }
 
+    #[test]
+    pub fn test_fixed_string_schema() {
+        use hdf5_types::TypeDescriptor;
+        use types::FixedAscii;
+
+        with_tmp_file(|file| {
+            // an empty attribute of an exact type, written later with a shorter value
+            let attr = file.new_attr::<FixedAscii<32>>().create("label").unwrap();
+            assert_eq!(
+                attr.dtype().unwrap().to_descriptor().unwrap(),
+                TypeDescriptor::FixedAscii(32)
+            );
+            assert_eq!(attr.dtype().unwrap().size(), 32);
+            let value = FixedAscii::<32>::from_ascii(b"short").unwrap();
+            attr.write_scalar(&value).unwrap();
+            let read: FixedAscii<32> = file.attr("label").unwrap().read_scalar().unwrap();
+            assert_eq!(read.as_str(), "short");
+
+            // the type can also be given at runtime, e.g. taken from an external schema
+            let attr = file
+                .new_attr_builder()
+                .empty_as(&TypeDescriptor::FixedAscii(8))
+                .shape(3)
+                .create("codes")
+                .unwrap();
+            assert_eq!(attr.shape(), vec![3]);
+            assert_eq!(attr.dtype().unwrap().size(), 8);
+            let codes: Vec<_> = ["a", "bc", "defghijk"]
+                .iter()
+                .map(|s| FixedAscii::<8>::from_ascii(s).unwrap())
+                .collect();
+            attr.write_raw(&codes).unwrap();
+            assert_eq!(attr.read_raw::<FixedAscii<8>>().unwrap(), codes);
+        })
+    }
+
     #[test]
     pub fn test_list_names() {
         with_tmp_file(|file| {
@@ -427,4 +571,35 @@ pub mod attribute_tests {
             assert!(attr_names.contains(&"bar".to_string()));
         })
     }
+
+    #[test]
+    pub fn test_for_each_attr() {
+        with_tmp_file(|file| {
+            for i in 0..20 {
+                file.new_attr::<u32>().create(&*format!("a{:02}", i)).unwrap();
+            }
+            let mut visited = vec![];
+            file.for_each_attr(|index, name| {
+                visited.push((index, name.to_owned()));
+                if index < 2 {
+                    IterAction::Continue
+                } else {
+                    IterAction::Stop
+                }
+            })
+            .unwrap();
+            assert_eq!(
+                visited,
+                vec![(0, "a00".to_owned()), (1, "a01".to_owned()), (2, "a02".to_owned())]
+            );
+
+            let mut count = 0;
+            file.for_each_attr(|_, _| {
+                count += 1;
+                IterAction::Continue
+            })
+            .unwrap();
+            assert_eq!(count, 20);
+        })
+    }
 }
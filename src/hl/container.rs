@@ -1,21 +1,35 @@
-use std::convert::TryInto;
+#[cfg(test)]
+use std::cell::Cell;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::io;
-use std::mem;
+use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
+use std::ptr;
+use std::slice;
 
-use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1};
+use ndarray::{Array, Array1, Array2, Array3, ArrayD, ArrayView, ArrayView1, ArrayViewMut};
 
 use hdf5_sys::h5a::{H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aread, H5Awrite};
 use hdf5_sys::h5d::{H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dwrite};
 use hdf5_sys::h5p::H5Pcreate;
-
+use hdf5_sys::h5t::H5Tget_member_index;
+use hdf5_types::{
+    CompoundField, CompoundType, OwnedDynValue, TypeDescriptor, VarLenAscii, VarLenUnicode,
+};
+
+use crate::hl::datatype::TypeClass;
+use crate::hl::plist::dataset_transfer::DatasetTransferBuilder;
+#[cfg(feature = "have-parallel")]
+use crate::hl::plist::dataset_transfer::{CollectiveOpt, IoMode};
+use crate::hl::selection::{RawHyperslab, RawSelection, RawSlice};
 use crate::internal_prelude::*;
 
 #[derive(Debug)]
 pub struct Reader<'a> {
     obj: &'a Container,
     conv: Conversion,
+    xfer: DatasetTransferBuilder,
 }
 
 impl<'a> Reader<'a> {
@@ -23,7 +37,7 @@ impl<'a> Reader<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, xfer: DatasetTransferBuilder::new() }
     }
 
     /// Set maximum allowed conversion level.
@@ -38,25 +52,93 @@ impl<'a> Reader<'a> {
         self
     }
 
+    /// Set an arithmetic expression (e.g. `"x/100"`) which HDF5 applies to each element
+    /// as part of the transfer.
+    ///
+    /// Data transforms are only supported for datasets of integer and floating-point types.
+    pub fn data_transform(mut self, expression: &str) -> Self {
+        self.xfer.data_transform(expression);
+        self
+    }
+
+    /// Set the size of the type conversion buffer in bytes (1 MiB by default); a larger
+    /// buffer may speed up reading data which requires conversion (e.g. compound types).
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.xfer.buffer_size(size);
+        self
+    }
+
+    /// Enable or disable the verification of checksums (error detection, e.g. via the
+    /// Fletcher32 filter) while reading.
+    ///
+    /// The checksums are verified by default and reading fails if a mismatch is detected;
+    /// disabling the check allows recovering the data from corrupted chunks.
+    pub fn edc_check(mut self, enable: bool) -> Self {
+        self.xfer.edc_check(enable);
+        self
+    }
+
+    /// Set the number of I/O vectors used for hyperslab selections (1024 by default); a larger
+    /// value may speed up reading slices made of many small blocks (e.g. strided reads).
+    pub fn hyper_vector_size(mut self, size: usize) -> Self {
+        self.xfer.hyper_vector_size(size);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Set the parallel I/O transfer mode (independent by default); only has effect on files
+    /// opened with the MPI-IO driver.
+    pub fn io_mode(mut self, mode: IoMode) -> Self {
+        self.xfer.io_mode(mode);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Set the low-level I/O used by collective transfers.
+    pub fn collective_opt(mut self, opt: CollectiveOpt) -> Self {
+        self.xfer.collective_opt(opt);
+        self
+    }
+
+    // Note on variable-length data: HDF5 allocates memory for varlen elements (arrays and
+    // strings) while converting them into `buf`. For datasets, the transfer plist is set up so
+    // that this memory comes from the same allocator that `hdf5-types` uses; `H5Aread` doesn't
+    // accept a transfer plist and uses the library allocator, which is plain `malloc` unless
+    // HDF5 is built with the memchecker (in which case "h5-alloc" should be enabled). The
+    // ownership of these allocations is then transferred to the resulting `VarLenArray`,
+    // `VarLenAscii` and `VarLenUnicode` values which free them on drop, so there's no need
+    // to call `H5Dvlen_reclaim` / `H5Treclaim` here.
     fn read_into_buf<T: H5Type>(
         &self, buf: *mut T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        self.read_into_buf_as(buf.cast(), &Datatype::from_type::<T>()?, fspace, mspace)
+    }
+
+    fn read_into_buf_as(
+        &self, buf: *mut c_void, mem_dtype: &Datatype, fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
     ) -> Result<()> {
         let file_dtype = self.obj.dtype()?;
-        let mem_dtype = Datatype::from_type::<T>()?;
-        file_dtype.ensure_convertible(&mem_dtype, self.conv)?;
+        // Identical types don't need a conversion path lookup; in this case HDF5 itself
+        // also skips the type conversion machinery and reads directly into the buffer.
+        if &file_dtype != mem_dtype {
+            file_dtype.ensure_convertible(mem_dtype, self.conv)?;
+        }
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
-            h5try!(H5Aread(obj_id, tp_id, buf.cast()));
+            ensure!(self.xfer.is_default(), "Transfer properties cannot be used on attributes");
+            h5try!(H5Aread(obj_id, tp_id, buf));
         } else {
             let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
             let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
-            let xfer =
-                PropertyList::from_id(h5call!(H5Pcreate(*crate::globals::H5P_DATASET_XFER))?)?;
+            let xfer = self.xfer.finish()?;
             if !hdf5_types::USING_H5_ALLOCATOR {
                 crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
             }
-            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf.cast()));
+            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf));
+            #[cfg(test)]
+            DATASET_READS.with(|reads| reads.set(reads.get() + 1));
         }
         Ok(())
     }
@@ -111,6 +193,60 @@ impl<'a> Reader<'a> {
         }
     }
 
+    /// Reads multiple slices of a dataset with a single read operation.
+    ///
+    /// If the slices are hyperslabs following each other in row-major order (e.g. blocks of
+    /// rows sorted by their first row), they're combined into one hyperslab selection;
+    /// otherwise, e.g. if they overlap or contain points, their elements are gathered into
+    /// one point selection. Either way, this is faster than reading each slice separately
+    /// when there are many small slices. The slices are returned in the order of the
+    /// selections, each with the same shape as it would have when read via `read_slice()`.
+    pub fn read_slices<T, S, I>(&self, selections: I) -> Result<Vec<ArrayD<T>>>
+    where
+        T: H5Type,
+        I: IntoIterator<Item = S>,
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        ensure!(!self.obj.is_attr(), "Slicing cannot be used on attribute datasets");
+
+        let obj_space = self.obj.space()?;
+        let shape = obj_space.shape();
+        ensure!(!shape.is_empty(), "Slicing cannot be used on scalar datasets");
+
+        let (mut raw_selections, mut out_shapes) = (vec![], vec![]);
+        for selection in selections {
+            let selection = selection.try_into()?;
+            out_shapes.push(selection.out_shape(&shape)?);
+            raw_selections.push(selection.into_raw(&shape)?);
+        }
+
+        let (fspace, sizes) = gather_selections(&obj_space, &raw_selections)?;
+        let total_size: Ix = sizes.iter().sum();
+        let mut buf = Vec::with_capacity(total_size);
+        if total_size > 0 {
+            let mspace = Dataspace::try_new(total_size)?;
+            self.read_into_buf(buf.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
+            unsafe {
+                buf.set_len(total_size);
+            }
+        }
+
+        let mut buf = buf.into_iter();
+        out_shapes
+            .into_iter()
+            .zip(sizes)
+            .map(|(out_shape, size)| {
+                let slice: Vec<_> = buf.by_ref().take(size).collect();
+                if size == 0 {
+                    Ok(unsafe { ArrayD::from_shape_vec_unchecked(out_shape, slice) })
+                } else {
+                    Ok(ArrayD::from_shape_vec(out_shape, slice)?)
+                }
+            })
+            .collect()
+    }
+
     /// Reads a dataset/attribute into an n-dimensional array.
     ///
     /// If the array has a fixed number of dimensions, it must match the dimensionality
@@ -138,6 +274,128 @@ impl<'a> Reader<'a> {
         })
     }
 
+    /// Reads a dataset/attribute into a vector of dynamically typed values (in row-major
+    /// order), using the native in-memory representation of the stored type.
+    ///
+    /// This works for any type that can be described by `TypeDescriptor`, including nested
+    /// variable-length types (e.g. variable-length arrays of variable-length arrays) which
+    /// can't be expressed via `VarLenArray`. Each value owns the variable-length data that
+    /// HDF5 allocates for it, including all of the nested buffers, and frees it when dropped.
+    pub fn read_raw_dyn(&self) -> Result<Vec<OwnedDynValue>> {
+        let desc = self.obj.dtype()?.to_descriptor()?.to_c_repr();
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let (size, elem_size) = (self.obj.space()?.size(), desc.size());
+        let mut buf = vec![0_u8; size * elem_size];
+        self.read_into_buf_as(buf.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+        Ok(buf
+            .chunks_exact(elem_size)
+            .map(|value| unsafe { OwnedDynValue::from_raw(desc.clone(), value.into()) })
+            .collect())
+    }
+
+    /// Reads a single member (field) of a dataset/attribute of compound type into an array.
+    ///
+    /// The in-memory type is a compound type containing only the requested member, so the
+    /// other members are not converted or copied into memory; the member value is converted
+    /// to `T` while reading if required.
+    pub fn read_field<T: H5Type>(&self, field: &str) -> Result<ArrayD<T>> {
+        let file_dtype = self.obj.dtype()?;
+        let class = file_dtype.type_class()?;
+        ensure!(class == TypeClass::Compound, "Expected a compound type, got {:?}", class);
+        let name = to_cstring(field)?;
+        let index = h5lock!(H5Tget_member_index(file_dtype.id(), name.as_ptr()));
+        ensure!(index >= 0, "Compound type has no member named {:?}", field);
+        let desc = TypeDescriptor::Compound(CompoundType {
+            fields: vec![CompoundField::typed::<T>(field, 0, 0)],
+            size: mem::size_of::<T>(),
+        });
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let shape = self.obj.get_shape()?;
+        let size = shape.iter().product();
+        let mut vec = Vec::<T>::with_capacity(size);
+        self.read_into_buf_as(vec.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+        unsafe {
+            vec.set_len(size);
+        }
+        Ok(ArrayD::from_shape_vec(shape, vec)?)
+    }
+
+    /// Reads a dataset/attribute of enum type as raw integer values, returning the
+    /// names and values of the enum members along with the data.
+    ///
+    /// This is useful if the stored enum doesn't match any Rust enum, so the values can be
+    /// mapped manually. Note that unsigned 64-bit values above `i64::MAX` wrap around.
+    pub fn read_enum_raw(&self) -> Result<(Vec<(String, i64)>, ArrayD<i64>)> {
+        let desc = self.obj.dtype()?.to_descriptor()?;
+        let enum_type = match desc {
+            TypeDescriptor::Enum(ref enum_type) => enum_type,
+            _ => fail!("Expected an enum type, got {}", desc),
+        };
+        let (size, signed) = (enum_type.size as usize, enum_type.signed);
+        let members = enum_type
+            .members
+            .iter()
+            .map(|m| Ok((m.name.clone(), enum_member_value(m.value, size, signed)?)))
+            .collect::<Result<_>>()?;
+        let mem_dtype = Datatype::from_descriptor(&desc)?;
+        let shape = self.obj.get_shape()?;
+        let mut buf = vec![0_u8; shape.iter().product::<usize>() * size];
+        self.read_into_buf_as(buf.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+        let values =
+            buf.chunks_exact(size).map(|v| enum_raw_value(v, signed)).collect::<Result<_>>()?;
+        Ok((members, ArrayD::from_shape_vec(shape, values)?))
+    }
+
+    /// Reads a dataset/attribute into a type-erased array whose element type is chosen
+    /// based on the stored type, see [`AnyArray`] for details.
+    pub fn read_any(&self) -> Result<AnyArray> {
+        use hdf5_types::{FloatSize, IntSize};
+        use TypeDescriptor as TD;
+
+        let desc = self.obj.dtype()?.to_descriptor()?;
+        Ok(match desc {
+            TD::Integer(IntSize::U1) => AnyArray::I8(self.read()?),
+            TD::Integer(IntSize::U2) => AnyArray::I16(self.read()?),
+            TD::Integer(IntSize::U4) => AnyArray::I32(self.read()?),
+            TD::Integer(IntSize::U8) => AnyArray::I64(self.read()?),
+            TD::Unsigned(IntSize::U1) => AnyArray::U8(self.read()?),
+            TD::Unsigned(IntSize::U2) => AnyArray::U16(self.read()?),
+            TD::Unsigned(IntSize::U4) => AnyArray::U32(self.read()?),
+            TD::Unsigned(IntSize::U8) => AnyArray::U64(self.read()?),
+            TD::Float(FloatSize::U4) => AnyArray::F32(self.read()?),
+            TD::Float(FloatSize::U8) => AnyArray::F64(self.read()?),
+            TD::Boolean => AnyArray::Bool(self.read()?),
+            TD::VarLenAscii => {
+                AnyArray::String(self.read::<VarLenAscii, _>()?.map(|s| s.as_str().to_owned()))
+            }
+            TD::VarLenUnicode => {
+                AnyArray::String(self.read::<VarLenUnicode, _>()?.map(|s| s.as_str().to_owned()))
+            }
+            TD::FixedAscii(len) | TD::FixedUnicode(len) => {
+                let mem_dtype = Datatype::from_descriptor(&desc)?;
+                let shape = self.obj.get_shape()?;
+                let size = shape.iter().product::<usize>();
+                let mut buf = vec![0_u8; size * len];
+                self.read_into_buf_as(buf.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+                let strings = if len == 0 {
+                    vec![String::new(); size]
+                } else {
+                    buf.chunks_exact(len)
+                        .map(|s| {
+                            let end = s.iter().position(|&c| c == 0).unwrap_or(len);
+                            String::from_utf8_lossy(&s[..end]).into_owned()
+                        })
+                        .collect()
+                };
+                AnyArray::String(ArrayD::from_shape_vec(shape, strings)?)
+            }
+            _ => {
+                let shape = self.obj.get_shape()?;
+                AnyArray::Other(ArrayD::from_shape_vec(shape, self.read_raw_dyn()?)?)
+            }
+        })
+    }
+
     /// Reads a dataset/attribute into a 1-dimensional array.
     ///
     /// The dataset/attribute must be 1-dimensional.
@@ -146,7 +404,8 @@ impl<'a> Reader<'a> {
     }
 
     /// Reads the given `slice` of the dataset into a 1-dimensional array.
-    /// The slice must yield a 1-dimensional result.
+    /// The slice must yield a 1-dimensional result (after the indexed axes are dropped),
+    /// otherwise an error is returned.
     pub fn read_slice_1d<T, S>(&self, selection: S) -> Result<Array1<T>>
     where
         T: H5Type,
@@ -163,8 +422,16 @@ impl<'a> Reader<'a> {
         self.read()
     }
 
+    /// Reads a dataset/attribute into a 3-dimensional array.
+    ///
+    /// The dataset/attribute must be 3-dimensional.
+    pub fn read_3d<T: H5Type>(&self) -> Result<Array3<T>> {
+        self.read()
+    }
+
     /// Reads the given `slice` of the dataset into a 2-dimensional array.
-    /// The slice must yield a 2-dimensional result.
+    /// The slice must yield a 2-dimensional result (after the indexed axes are dropped),
+    /// otherwise an error is returned.
     pub fn read_slice_2d<T, S>(&self, selection: S) -> Result<Array2<T>>
     where
         T: H5Type,
@@ -179,6 +446,66 @@ impl<'a> Reader<'a> {
         self.read()
     }
 
+    /// Reads a dataset/attribute of fixed-size arrays (e.g. `[f32; 3]`) into an array of
+    /// their elements, with the array lengths appended to the shape as trailing axes.
+    ///
+    /// For example, a dataset of shape `(N,)` storing `[f32; 3]` values is read into an
+    /// `(N, 3)` array of `T`; nested fixed-size arrays add one axis per nesting level.
+    /// The elements are converted to `T` while reading if required.
+    pub fn read_expanded<T: H5Type>(&self) -> Result<ArrayD<T>> {
+        let mut shape = self.obj.get_shape()?;
+        let mut lens = vec![];
+        let mut desc = self.obj.dtype()?.to_descriptor()?;
+        while let TypeDescriptor::FixedArray(ty, len) = desc {
+            lens.push(len);
+            desc = *ty;
+        }
+        ensure!(!lens.is_empty(), "Expected a fixed-size array datatype, got {}", desc);
+        let mem_desc = lens
+            .iter()
+            .rev()
+            .fold(T::type_descriptor(), |ty, &len| TypeDescriptor::FixedArray(Box::new(ty), len));
+        let mem_dtype = Datatype::from_descriptor(&mem_desc)?;
+        shape.extend(lens);
+        let size = shape.iter().product();
+        let mut vec = Vec::<T>::with_capacity(size);
+        self.read_into_buf_as(vec.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+        unsafe {
+            vec.set_len(size);
+        }
+        Ok(ArrayD::from_shape_vec(shape, vec)?)
+    }
+
+    /// Reads a dataset/attribute into an uninitialized array view, returning the same
+    /// memory as an initialized view.
+    ///
+    /// The shape of the view must match the shape of the dataset/attribute exactly, and
+    /// the view must be in standard layout. HDF5 overwrites every element of the buffer
+    /// on a successful read, so this allows skipping the initialization of large output
+    /// buffers. If an error is returned, the contents of the buffer must still be treated
+    /// as uninitialized (they may have been partially written to).
+    pub fn read_into_uninit<'b, T, D>(
+        &self, mut buf: ArrayViewMut<'b, MaybeUninit<T>, D>,
+    ) -> Result<ArrayViewMut<'b, T, D>>
+    where
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        ensure!(
+            buf.is_standard_layout(),
+            "output array is not in standard layout or is not contiguous"
+        );
+        let src = &*self.obj.get_shape()?;
+        let dst = buf.shape();
+        if src != dst {
+            fail!("shape mismatch when reading: source = {:?}, memory = {:?}", src, dst);
+        }
+        let ptr = buf.as_mut_ptr().cast::<T>();
+        self.read_into_buf(ptr, None, None)?;
+        // MaybeUninit<T> has the same layout as T, and all elements have been initialized
+        Ok(unsafe { ArrayViewMut::from_shape_ptr(buf.raw_dim(), ptr) })
+    }
+
     /// Reads a scalar dataset/attribute.
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
         let obj_ndim = self.obj.get_shape()?.ndim();
@@ -188,10 +515,198 @@ impl<'a> Reader<'a> {
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    /// Number of `H5Dread` calls made by the current thread.
+    static DATASET_READS: Cell<usize> = Cell::new(0);
+}
+
+/// Selects the elements of all `selections` (in order) in a copy of `space`, returning it
+/// along with the number of elements in each selection.
+///
+/// Finite hyperslabs whose elements all follow the ones of the previous hyperslab in
+/// row-major order are combined into a single hyperslab selection, so they're transferred
+/// in the order of the selections; otherwise, all elements are selected as points.
+fn gather_selections(
+    space: &Dataspace, selections: &[RawSelection],
+) -> Result<(Dataspace, Vec<Ix>)> {
+    let shape = space.shape();
+    let linear = |coords: &[Ix]| coords.iter().zip(&shape).fold(0, |acc, (&c, &d)| acc * d + c);
+
+    let (mut hypers, mut sizes) = (vec![], vec![]);
+    // linear index past the last element of the previous hyperslab
+    let mut end = 0;
+    for selection in selections {
+        let hyper = match selection {
+            RawSelection::None => {
+                sizes.push(0);
+                continue;
+            }
+            RawSelection::All => RawHyperslab::from(
+                shape.iter().map(|&dim| RawSlice::new(0, 1, Some(dim), 1)).collect::<Vec<_>>(),
+            ),
+            RawSelection::RegularHyperslab(hyper) => hyper.clone(),
+            _ => break,
+        };
+        let counts = match hyper.iter().map(|s| s.count).collect::<Option<Vec<_>>>() {
+            Some(counts) => counts,
+            None => break,
+        };
+        let size = hyper.iter().zip(&counts).map(|(s, &count)| count * s.block).product();
+        if size == 0 {
+            sizes.push(0);
+            continue;
+        }
+        let first: Vec<_> = hyper.iter().map(|s| s.start).collect();
+        let last: Vec<_> = hyper
+            .iter()
+            .zip(&counts)
+            .map(|(s, &count)| s.start + (count - 1) * s.step + s.block - 1)
+            .collect();
+        if !hypers.is_empty() && linear(&first) < end {
+            break; // overlapping or out of order
+        }
+        end = linear(&last) + 1;
+        hypers.push(hyper);
+        sizes.push(size);
+    }
+    if sizes.len() == selections.len() {
+        return Ok((space.select_hyperslab_union(&hypers)?, sizes));
+    }
+
+    let (mut coords, mut sizes) = (vec![], vec![]);
+    for selection in selections {
+        sizes.push(selection.append_points(&shape, &mut coords)?);
+    }
+    let total_size: Ix = sizes.iter().sum();
+    let space = if total_size > 0 {
+        space.select(Array2::from_shape_vec((total_size, shape.len()), coords)?)?
+    } else {
+        space.select_raw(RawSelection::None)?
+    };
+    Ok((space, sizes))
+}
+
+/// Describes the memory layout of a strided view as a (larger) row-major memory dataspace
+/// and a regular hyperslab selection in it, if possible; returns `None` if the elements of
+/// the view are not laid out in increasing row-major order (e.g. if the view is transposed
+/// or has negative strides). The strides are measured in elements.
+fn strided_mem_layout(shape: &[Ix], strides: &[isize]) -> Option<(Vec<Ix>, Vec<RawSlice>)> {
+    let ndim = shape.len();
+    if ndim == 0 || shape.contains(&0) {
+        return None;
+    }
+    let mut dims = vec![0; ndim];
+    let mut slices = vec![RawSlice::new(0, 1, Some(1), 1); ndim];
+    // number of elements between consecutive indices along the current memory axis
+    let mut inner: Ix = 1;
+    for k in (0..ndim).rev() {
+        let step = if shape[k] == 1 {
+            1
+        } else {
+            let stride = usize::try_from(strides[k]).ok().filter(|&s| s != 0)?;
+            if stride % inner != 0 {
+                return None;
+            }
+            stride / inner
+        };
+        let extent = (shape[k] - 1) * step + 1;
+        dims[k] = if k > 0 && shape[k - 1] != 1 {
+            // the row length is determined by the stride of the next outer axis
+            let stride = usize::try_from(strides[k - 1]).ok()?;
+            if stride % inner != 0 || stride / inner < extent {
+                return None;
+            }
+            stride / inner
+        } else {
+            extent
+        };
+        slices[k] = RawSlice::new(0, step, Some(shape[k]), 1);
+        inner *= dims[k];
+    }
+    Some((dims, slices))
+}
+
+/// Converts the value of an enum member (see `EnumMember::value`) of the given size to `i64`.
+fn enum_member_value(value: u64, size: usize, signed: bool) -> Result<i64> {
+    // truncate numerically (rather than via the bytes) so that this works on any endianness
+    Ok(match (size, signed) {
+        (1, true) => i64::from(value as i8),
+        (1, false) => i64::from(value as u8),
+        (2, true) => i64::from(value as i16),
+        (2, false) => i64::from(value as u16),
+        (4, true) => i64::from(value as i32),
+        (4, false) => i64::from(value as u32),
+        (8, _) => value as _,
+        _ => fail!("Unsupported enum size: {}", size),
+    })
+}
+
+/// Converts a native-endian enum value of the given size to `i64`.
+fn enum_raw_value(bytes: &[u8], signed: bool) -> Result<i64> {
+    Ok(match (bytes.len(), signed) {
+        (1, true) => i8::from_ne_bytes([bytes[0]]).into(),
+        (1, false) => u8::from_ne_bytes([bytes[0]]).into(),
+        (2, true) => i16::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        (2, false) => u16::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        (4, true) => i32::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        (4, false) => u32::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        (8, true) => i64::from_ne_bytes(bytes.try_into().unwrap()),
+        (8, false) => u64::from_ne_bytes(bytes.try_into().unwrap()) as _,
+        _ => fail!("Unsupported enum size: {}", bytes.len()),
+    })
+}
+
+/// A type-erased array read from a dataset/attribute via [`Reader::read_any()`].
+///
+/// The variant is chosen based on the stored type: integers, floats and booleans are read
+/// as arrays of the corresponding native Rust type, strings of all kinds (fixed-length or
+/// variable-length, ASCII or UTF-8) are read as arrays of `String`, and values of all other
+/// types (e.g. compounds, enums and arrays) are read as dynamically typed values, each of
+/// which contains the raw bytes and the type descriptor of the value.
+#[derive(Debug)]
+pub enum AnyArray {
+    I8(ArrayD<i8>),
+    I16(ArrayD<i16>),
+    I32(ArrayD<i32>),
+    I64(ArrayD<i64>),
+    U8(ArrayD<u8>),
+    U16(ArrayD<u16>),
+    U32(ArrayD<u32>),
+    U64(ArrayD<u64>),
+    F32(ArrayD<f32>),
+    F64(ArrayD<f64>),
+    Bool(ArrayD<bool>),
+    String(ArrayD<String>),
+    Other(ArrayD<OwnedDynValue>),
+}
+
+impl AnyArray {
+    /// Returns the shape of the array.
+    pub fn shape(&self) -> &[Ix] {
+        match self {
+            Self::I8(arr) => arr.shape(),
+            Self::I16(arr) => arr.shape(),
+            Self::I32(arr) => arr.shape(),
+            Self::I64(arr) => arr.shape(),
+            Self::U8(arr) => arr.shape(),
+            Self::U16(arr) => arr.shape(),
+            Self::U32(arr) => arr.shape(),
+            Self::U64(arr) => arr.shape(),
+            Self::F32(arr) => arr.shape(),
+            Self::F64(arr) => arr.shape(),
+            Self::Bool(arr) => arr.shape(),
+            Self::String(arr) => arr.shape(),
+            Self::Other(arr) => arr.shape(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Writer<'a> {
     obj: &'a Container,
     conv: Conversion,
+    xfer: DatasetTransferBuilder,
 }
 
 impl<'a> Writer<'a> {
@@ -199,7 +714,7 @@ impl<'a> Writer<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, xfer: DatasetTransferBuilder::new() }
     }
 
     /// Set maximum allowed conversion level.
@@ -214,6 +729,44 @@ impl<'a> Writer<'a> {
         self
     }
 
+    /// Set an arithmetic expression (e.g. `"x/100"`) which HDF5 applies to each element
+    /// as part of the transfer.
+    ///
+    /// Data transforms are only supported for datasets of integer and floating-point types.
+    pub fn data_transform(mut self, expression: &str) -> Self {
+        self.xfer.data_transform(expression);
+        self
+    }
+
+    /// Set the size of the type conversion buffer in bytes (1 MiB by default); a larger
+    /// buffer may speed up writing data which requires conversion (e.g. compound types).
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.xfer.buffer_size(size);
+        self
+    }
+
+    /// Set the number of I/O vectors used for hyperslab selections (1024 by default); a larger
+    /// value may speed up writing slices made of many small blocks (e.g. strided writes).
+    pub fn hyper_vector_size(mut self, size: usize) -> Self {
+        self.xfer.hyper_vector_size(size);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Set the parallel I/O transfer mode (independent by default); only has effect on files
+    /// opened with the MPI-IO driver.
+    pub fn io_mode(mut self, mode: IoMode) -> Self {
+        self.xfer.io_mode(mode);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Set the low-level I/O used by collective transfers.
+    pub fn collective_opt(mut self, opt: CollectiveOpt) -> Self {
+        self.xfer.collective_opt(opt);
+        self
+    }
+
     fn write_from_buf<T: H5Type>(
         &self, buf: *const T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
@@ -223,15 +776,77 @@ impl<'a> Writer<'a> {
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
+            ensure!(self.xfer.is_default(), "Transfer properties cannot be used on attributes");
             h5try!(H5Awrite(obj_id, tp_id, buf.cast()));
         } else {
             let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
             let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
-            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf.cast()));
+            let xfer = if self.xfer.is_default() { None } else { Some(self.xfer.finish()?) };
+            let xfer_id = xfer.as_ref().map_or(H5P_DEFAULT, |p| p.id());
+            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, xfer_id, buf.cast()));
+        }
+        Ok(())
+    }
+
+    /// Makes sure that fixed-length strings fit into the destination if its fixed-length
+    /// string type is shorter, since they would otherwise be silently truncated by the
+    /// library (possibly in the middle of a multi-byte UTF-8 character).
+    fn check_string_lengths<'v, T, I>(&self, values: I) -> Result<()>
+    where
+        T: H5Type + 'v,
+        I: IntoIterator<Item = &'v T>,
+    {
+        let len = match T::type_descriptor() {
+            TypeDescriptor::FixedAscii(len) | TypeDescriptor::FixedUnicode(len) => len,
+            _ => return Ok(()),
+        };
+        let max_len = match self.obj.dtype()?.to_descriptor()? {
+            TypeDescriptor::FixedAscii(n) | TypeDescriptor::FixedUnicode(n) if n < len => n,
+            _ => return Ok(()),
+        };
+        for value in values {
+            // fixed-length strings are stored as null-padded byte arrays
+            let bytes = unsafe { slice::from_raw_parts((value as *const T).cast::<u8>(), len) };
+            let value_len = bytes.iter().position(|&c| c == 0).unwrap_or(len);
+            ensure!(
+                value_len <= max_len,
+                "String of length {} does not fit into the destination string type of length {}",
+                value_len,
+                max_len
+            );
         }
         Ok(())
     }
 
+    /// Calls `func` with a pointer to the elements of `view` and, if the elements are not
+    /// contiguous, a memory dataspace with a selection describing their layout.
+    ///
+    /// Views in standard layout are passed through as is; strided views whose elements
+    /// are laid out in C order with gaps (e.g. ones sliced with steps from a C-ordered
+    /// array) are passed through along with a hyperslab selection, so that HDF5 gathers
+    /// the elements directly from memory. Other views (e.g. Fortran-ordered, transposed
+    /// or with negative strides) and all views written to attributes (which don't support
+    /// memory selections) are copied into a temporary C-ordered buffer first.
+    fn with_mem_layout<T, D, F>(&self, view: &ArrayView<T, D>, func: F) -> Result<()>
+    where
+        D: ndarray::Dimension,
+        F: FnOnce(*const T, Option<&Dataspace>) -> Result<()>,
+    {
+        if view.is_standard_layout() {
+            return func(view.as_ptr(), None);
+        }
+        if !self.obj.is_attr() {
+            if let Some((dims, slices)) = strided_mem_layout(view.shape(), view.strides()) {
+                let mspace = Dataspace::try_new(dims)?.select_raw(slices)?;
+                return func(view.as_ptr(), Some(&mspace));
+            }
+        }
+        // bitwise copies which are never dropped, so `T: Clone` is not required
+        let buf: Vec<MaybeUninit<T>> =
+            view.iter().map(|x| unsafe { ptr::read((x as *const T).cast()) }).collect();
+        func(buf.as_ptr().cast(), None)
+    }
+
     /// Writes all data from the array `arr` into the given `slice` of the target dataset.
     /// The shape of `arr` must match the shape the set of elements included in the slice.
     /// If the array has a fixed number of dimensions, it must match the dimensionality of
@@ -255,8 +870,8 @@ impl<'a> Writer<'a> {
         let fspace = obj_space.select(selection)?;
         let view = arr.into();
 
+        let out_ndim = out_shape.len();
         if let Some(ndim) = D::NDIM {
-            let out_ndim = out_shape.len();
             ensure!(ndim == out_ndim, "Selection ndim ({}) != array ndim ({})", out_ndim, ndim);
         } else {
             let fsize = fspace.selection_size();
@@ -267,26 +882,36 @@ impl<'a> Writer<'a> {
                 fsize
             );
             ensure!(
-                view.shape() == out_shape.as_slice(),
+                view.ndim() == out_ndim,
                 "Shape mismatch: memory ({:?}) != destination ({:?})",
                 view.shape(),
                 out_shape
             );
         }
+        // check the whole shape (not just the size) so that the data doesn't get silently
+        // reshaped, and report the first mismatched axis
+        if let Some(axis) = (0..out_ndim).find(|&i| view.shape()[i] != out_shape[i]) {
+            fail!(
+                "Shape mismatch along axis {}: memory has {} elements, destination has {} \
+                 (memory shape: {:?}, destination shape: {:?})",
+                axis,
+                view.shape()[axis],
+                out_shape[axis],
+                view.shape(),
+                out_shape
+            );
+        }
 
         if out_size == 0 {
             Ok(())
         } else if obj_space.ndim() == 0 {
             self.write(view)
         } else {
+            self.check_string_lengths(view.iter())?;
             let mspace = Dataspace::try_new(view.shape())?;
-            // TODO: support strided arrays (C-ordering we have to require regardless)
-            ensure!(
-                view.is_standard_layout(),
-                "Input array is not in standard layout or non-contiguous"
-            );
-
-            self.write_from_buf(view.as_ptr(), Some(&fspace), Some(&mspace))
+            self.with_mem_layout(&view, |buf, strided| {
+                self.write_from_buf(buf, Some(&fspace), Some(strided.unwrap_or(&mspace)))
+            })
         }
     }
 
@@ -294,6 +919,9 @@ impl<'a> Writer<'a> {
     ///
     /// The shape of the view must match the shape of the dataset/attribute exactly.
     /// The input argument must be convertible to an array view (this includes slices).
+    /// Strided views (e.g. ones sliced with steps) are written directly from memory where
+    /// possible; other views which are not in standard layout (e.g. Fortran-ordered arrays)
+    /// are copied into a temporary C-ordered buffer before writing.
     pub fn write<'b, A, T, D>(&self, arr: A) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
@@ -301,18 +929,14 @@ impl<'a> Writer<'a> {
         D: ndarray::Dimension,
     {
         let view = arr.into();
-        ensure!(
-            view.is_standard_layout(),
-            "input array is not in standard layout or is not contiguous"
-        );
-
         let src = view.shape();
         let dst = &*self.obj.get_shape()?;
         if src != dst {
             fail!("shape mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
         }
+        self.check_string_lengths(view.iter())?;
 
-        self.write_from_buf(view.as_ptr(), None, None)
+        self.with_mem_layout(&view, |buf, mspace| self.write_from_buf(buf, None, mspace))
     }
 
     /// Writes a 1-dimensional array view into a dataset/attribute in memory order.
@@ -326,23 +950,20 @@ impl<'a> Writer<'a> {
         T: H5Type,
     {
         let view = arr.into();
-        ensure!(
-            view.is_standard_layout(),
-            "input array is not in standard layout or is not contiguous"
-        );
-
         let src = view.len();
         let dst = self.obj.get_shape()?.size();
         if src != dst {
             fail!("length mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
         }
-        self.write_from_buf(view.as_ptr(), None, None)
+        self.check_string_lengths(view.iter())?;
+        self.with_mem_layout(&view, |buf, mspace| self.write_from_buf(buf, None, mspace))
     }
 
     /// Writes a scalar dataset/attribute.
     pub fn write_scalar<T: H5Type>(&self, val: &T) -> Result<()> {
         let ndim = self.obj.get_shape()?.ndim();
         ensure!(ndim == 0, "ndim mismatch: expected scalar, got {}", ndim);
+        self.check_string_lengths(Some(val))?;
         self.write_from_buf(val as *const _, None, None)
     }
 }
@@ -391,6 +1012,10 @@ impl io::Read for ByteReader {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         let pos = self.pos as usize;
         let amt = std::cmp::min(buf.len(), self.remaining_len());
+        if amt == 0 {
+            // at (or past) the end of the dataset, or empty buffer
+            return Ok(0);
+        }
         let selection = Selection::new(pos..pos + amt);
         let out_shape = selection.out_shape(&self.obj_space.shape())?;
         let fspace = self.obj_space.select(selection)?;
@@ -530,9 +1155,15 @@ impl Container {
         self.space().ok().map_or(0, |s| s.ndim())
     }
 
-    /// Returns the total number of elements in the dataset/attribute.
+    /// Returns the maximum shape of the dataset/attribute (`None` for unlimited dimensions).
+    pub fn maxdims(&self) -> Vec<Option<Ix>> {
+        self.space().ok().map_or_else(Vec::new, |s| s.maxdims())
+    }
+
+    /// Returns the total number of elements in the dataset/attribute (1 for scalars and
+    /// 0 for null dataspaces).
     pub fn size(&self) -> usize {
-        self.shape().iter().product()
+        self.space().ok().map_or(0, |s| s.size())
     }
 
     /// Returns whether this dataset/attribute is a scalar.
@@ -554,6 +1185,10 @@ impl Container {
     ///
     /// If the array has a fixed number of dimensions, it must match the dimensionality
     /// of the dataset/attribute.
+    ///
+    /// The in-memory type is `T`; if it's different from the stored type (e.g. reading
+    /// an `i16` dataset as `f64`), the data is converted by HDF5 while reading. Use
+    /// `as_reader()` to limit the allowed conversions.
     pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
         self.as_reader().read()
     }
@@ -563,6 +1198,30 @@ impl Container {
         self.as_reader().read_raw()
     }
 
+    /// Reads a dataset/attribute into a vector of dynamically typed values (in row-major
+    /// order), see [`Reader::read_raw_dyn()`] for details.
+    pub fn read_raw_dyn(&self) -> Result<Vec<OwnedDynValue>> {
+        self.as_reader().read_raw_dyn()
+    }
+
+    /// Reads a single member (field) of a dataset/attribute of compound type into an array,
+    /// see [`Reader::read_field()`] for details.
+    pub fn read_field<T: H5Type>(&self, field: &str) -> Result<ArrayD<T>> {
+        self.as_reader().read_field(field)
+    }
+
+    /// Reads a dataset/attribute of enum type as raw integer values, see
+    /// [`Reader::read_enum_raw()`] for details.
+    pub fn read_enum_raw(&self) -> Result<(Vec<(String, i64)>, ArrayD<i64>)> {
+        self.as_reader().read_enum_raw()
+    }
+
+    /// Reads a dataset/attribute into a type-erased array whose element type is chosen
+    /// based on the stored type, see [`AnyArray`] for details.
+    pub fn read_any(&self) -> Result<AnyArray> {
+        self.as_reader().read_any()
+    }
+
     /// Reads a dataset/attribute into a 1-dimensional array.
     ///
     /// The dataset/attribute must be 1-dimensional.
@@ -571,7 +1230,8 @@ impl Container {
     }
 
     /// Reads the given `slice` of the dataset into a 1-dimensional array.
-    /// The slice must yield a 1-dimensional result.
+    /// The slice must yield a 1-dimensional result (after the indexed axes are dropped),
+    /// otherwise an error is returned.
     pub fn read_slice_1d<T, S>(&self, selection: S) -> Result<Array1<T>>
     where
         T: H5Type,
@@ -588,8 +1248,16 @@ impl Container {
         self.as_reader().read_2d()
     }
 
+    /// Reads a dataset/attribute into a 3-dimensional array.
+    ///
+    /// The dataset/attribute must be 3-dimensional.
+    pub fn read_3d<T: H5Type>(&self) -> Result<Array3<T>> {
+        self.as_reader().read_3d()
+    }
+
     /// Reads the given `slice` of the dataset into a 2-dimensional array.
-    /// The slice must yield a 2-dimensional result.
+    /// The slice must yield a 2-dimensional result (after the indexed axes are dropped),
+    /// otherwise an error is returned.
     pub fn read_slice_2d<T, S>(&self, selection: S) -> Result<Array2<T>>
     where
         T: H5Type,
@@ -619,6 +1287,41 @@ impl Container {
         self.as_reader().read_slice(selection)
     }
 
+    /// Reads multiple slices of a dataset with a single read operation.
+    ///
+    /// See [`Reader::read_slices`] for details.
+    pub fn read_slices<T, S, I>(&self, selections: I) -> Result<Vec<ArrayD<T>>>
+    where
+        T: H5Type,
+        I: IntoIterator<Item = S>,
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        self.as_reader().read_slices(selections)
+    }
+
+    /// Reads a dataset/attribute of fixed-size arrays into an array of their elements,
+    /// with the array lengths appended to the shape as trailing axes.
+    ///
+    /// See [`Reader::read_expanded`] for details.
+    pub fn read_expanded<T: H5Type>(&self) -> Result<ArrayD<T>> {
+        self.as_reader().read_expanded()
+    }
+
+    /// Reads a dataset/attribute into an uninitialized array view, returning the same
+    /// memory as an initialized view.
+    ///
+    /// See [`Reader::read_into_uninit`] for details.
+    pub fn read_into_uninit<'b, T, D>(
+        &self, buf: ArrayViewMut<'b, MaybeUninit<T>, D>,
+    ) -> Result<ArrayViewMut<'b, T, D>>
+    where
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        self.as_reader().read_into_uninit(buf)
+    }
+
     /// Reads a scalar dataset/attribute.
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
         self.as_reader().read_scalar()
@@ -671,3 +1374,56 @@ impl Container {
         self.as_writer().write_scalar(val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use std::convert::TryFrom;
+
+    use ndarray::{s, Array2};
+
+    use super::{gather_selections, DATASET_READS};
+    use crate::hl::selection::RawSelection;
+    use crate::internal_prelude::*;
+
+    #[test]
+    fn test_read_slices_single_read() {
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((100, 40), |(i, j)| (i * 1000 + j) as i32);
+            let ds = file.new_dataset_builder().with_data(&arr).create("x").unwrap();
+            let space = ds.space().unwrap();
+            let raw = |sel: Selection| sel.into_raw(ds.shape()).unwrap();
+
+            // sorted, non-overlapping slabs are read as a single hyperslab selection
+            let slabs: Vec<_> = (0..20).map(|i| s![i * 5..i * 5 + 2, 3..10;2]).collect();
+            let raw_slabs: Vec<_> =
+                slabs.iter().map(|&slab| raw(Selection::try_from(slab).unwrap())).collect();
+            let (fspace, sizes) = gather_selections(&space, &raw_slabs).unwrap();
+            assert!(matches!(
+                fspace.get_raw_selection().unwrap(),
+                RawSelection::ComplexHyperslab | RawSelection::RegularHyperslab(_)
+            ));
+            assert_eq!(sizes, vec![8; 20]);
+
+            let reads = DATASET_READS.with(Cell::get);
+            let slices = ds.read_slices::<i32, _, _>(slabs.iter().copied()).unwrap();
+            assert_eq!(DATASET_READS.with(Cell::get), reads + 1);
+            for (slice, slab) in slices.iter().zip(&slabs) {
+                assert_eq!(slice, &arr.slice(slab).into_dyn());
+            }
+
+            // interleaving (or overlapping) slabs fall back to points
+            let raw_slabs = vec![raw((0..2, 20..40).into()), raw((0..2, 0..20).into())];
+            let (fspace, sizes) = gather_selections(&space, &raw_slabs).unwrap();
+            assert!(matches!(fspace.get_raw_selection().unwrap(), RawSelection::Points(_)));
+            assert_eq!(sizes, vec![40, 40]);
+            let reads = DATASET_READS.with(Cell::get);
+            let slices =
+                ds.read_slices::<i32, _, _>(vec![s![0..2, 20..40], s![0..2, 0..20]]).unwrap();
+            assert_eq!(DATASET_READS.with(Cell::get), reads + 1);
+            assert_eq!(slices[0], arr.slice(s![0..2, 20..40]).into_dyn());
+            assert_eq!(slices[1], arr.slice(s![0..2, 0..20]).into_dyn());
+        })
+    }
+}
@@ -1,31 +1,45 @@
+use std::convert::TryInto;
 use std::fmt::{self, Debug};
+use std::io;
 use std::ops::Deref;
+#[cfg(feature = "mmap")]
+use std::{fs, marker::PhantomData, mem, slice};
 
+#[cfg(feature = "mmap")]
+use memmap2::{Mmap, MmapOptions};
 use ndarray::{self, ArrayView};
+#[cfg(feature = "mmap")]
+use ndarray::{ArrayViewD, IxDyn};
 
 use hdf5_sys::h5::HADDR_UNDEF;
 use hdf5_sys::h5d::{
-    H5Dcreate2, H5Dcreate_anon, H5Dget_access_plist, H5Dget_create_plist, H5Dget_offset,
-    H5Dset_extent,
+    H5Dcreate2, H5Dcreate_anon, H5Dfill, H5Dget_access_plist, H5Dget_create_plist, H5Dget_offset,
+    H5Dset_extent, H5Dvlen_get_buf_size, H5Dwrite,
 };
+#[cfg(feature = "1.10.0")]
+use hdf5_sys::h5d::{H5Dflush, H5Drefresh};
 #[cfg(feature = "1.10.5")]
 use hdf5_sys::h5d::{H5Dget_chunk_info, H5Dget_num_chunks};
 use hdf5_sys::h5l::H5Ldelete;
-use hdf5_sys::h5p::H5P_DEFAULT;
+use hdf5_sys::h5p::{H5Pget_fill_value, H5P_DEFAULT};
+use hdf5_sys::h5t::{H5T_order_t, H5Tset_order};
 use hdf5_sys::h5z::H5Z_filter_t;
 use hdf5_types::{OwnedDynValue, TypeDescriptor};
 
+use crate::hl::datatype::ByteOrder;
 #[cfg(feature = "blosc")]
 use crate::hl::filters::{Blosc, BloscShuffle};
-use crate::hl::filters::{Filter, SZip, ScaleOffset};
+use crate::hl::filters::{Filter, PipelineFilter, SZip, ScaleOffset};
 #[cfg(feature = "1.10.0")]
 use crate::hl::plist::dataset_access::VirtualView;
 use crate::hl::plist::dataset_access::{DatasetAccess, DatasetAccessBuilder};
 #[cfg(feature = "1.10.0")]
 use crate::hl::plist::dataset_create::ChunkOpts;
 use crate::hl::plist::dataset_create::{
-    AllocTime, AttrCreationOrder, DatasetCreate, DatasetCreateBuilder, FillTime, Layout,
+    AllocTime, AttrCreationOrder, DatasetCreate, DatasetCreateBuilder, FillTime, FillValue, Layout,
 };
+#[cfg(feature = "mmap")]
+use crate::hl::plist::file_access::FileDriver;
 use crate::hl::plist::link_create::{CharEncoding, LinkCreate, LinkCreateBuilder};
 use crate::internal_prelude::*;
 
@@ -180,11 +194,75 @@ impl Dataset {
         }
     }
 
+    /// Returns the size of the dataset data in bytes as it would be stored without any
+    /// filters applied, i.e. the number of elements times the size of the stored type.
+    ///
+    /// Note that for variable-length types this doesn't include the variable-length data.
+    pub fn logical_size(&self) -> u64 {
+        let type_size = self.dtype().map_or(0, |dtype| dtype.size());
+        (self.size() * type_size) as _
+    }
+
+    /// Returns the achieved compression ratio, that is, `logical_size()` over `storage_size()`.
+    ///
+    /// If no storage has been allocated yet (e.g. for empty datasets), `1.0` is returned.
+    pub fn compression_ratio(&self) -> f64 {
+        match self.storage_size() {
+            0 => 1.0,
+            storage_size => self.logical_size() as f64 / storage_size as f64,
+        }
+    }
+
     /// Returns default fill value for the dataset if such value is set.
     pub fn fill_value(&self) -> Result<Option<OwnedDynValue>> {
         h5lock!(self.dcpl()?.get_fill_value(&self.dtype()?.to_descriptor()?))
     }
 
+    /// Resets the selected region of the dataset to the fill value of the dataset.
+    ///
+    /// This requires the fill value to be defined (either the library default one or
+    /// one set via `DatasetBuilder::fill_value()`).
+    pub fn fill_region<S>(&self, selection: S) -> Result<()>
+    where
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        let selection = selection.try_into()?;
+        h5lock!({
+            let dcpl = self.dcpl()?;
+            ensure!(
+                dcpl.fill_value_defined() != FillValue::Undefined,
+                "Fill value is not defined for the dataset"
+            );
+            let fspace = self.space()?.select(selection)?;
+            let size = fspace.selection_size();
+            if size == 0 {
+                return Ok(());
+            }
+            let mem_dtype = Datatype::from_descriptor(&self.dtype()?.to_descriptor()?)?;
+            let mut fill = vec![0_u8; mem_dtype.size()];
+            h5try!(H5Pget_fill_value(dcpl.id(), mem_dtype.id(), fill.as_mut_ptr().cast()));
+            let mspace = Dataspace::try_new(size)?;
+            let mut buf = vec![0_u8; size * mem_dtype.size()];
+            h5try!(H5Dfill(
+                fill.as_ptr().cast(),
+                mem_dtype.id(),
+                buf.as_mut_ptr().cast(),
+                mem_dtype.id(),
+                mspace.id()
+            ));
+            h5try!(H5Dwrite(
+                self.id(),
+                mem_dtype.id(),
+                mspace.id(),
+                fspace.id(),
+                H5P_DEFAULT,
+                buf.as_ptr().cast()
+            ));
+            Ok(())
+        })
+    }
+
     /// Resizes the dataset to a new shape.
     pub fn resize<D: Dimension>(&self, shape: D) -> Result<()> {
         let mut dims: Vec<hsize_t> = vec![];
@@ -195,10 +273,305 @@ impl Dataset {
         Ok(())
     }
 
+    #[cfg(feature = "1.10.0")]
+    /// Flushes all buffers associated with the dataset to disk.
+    ///
+    /// In SWMR mode, this makes the data written so far (and the new extent of the dataset)
+    /// visible to the readers.
+    pub fn flush(&self) -> Result<()> {
+        h5call!(H5Dflush(self.id())).and(Ok(()))
+    }
+
+    #[cfg(feature = "1.10.0")]
+    /// Refreshes the dataset by clearing the cached metadata and reloading it from disk.
+    ///
+    /// In SWMR mode, readers should call this to pick up the changes (e.g. the new extent
+    /// of the dataset) that were flushed by the writer since the dataset was opened.
+    pub fn refresh(&self) -> Result<()> {
+        h5call!(H5Drefresh(self.id())).and(Ok(()))
+    }
+
+    /// Creates `ByteWriter` which implements [`Write`](std::io::Write) by appending
+    /// to the end of the dataset.
+    ///
+    /// ``ByteWriter`` only supports 1-D resizable `u8` datasets.
+    pub fn as_byte_writer(&self) -> Result<ByteWriter> {
+        ByteWriter::new(self)
+    }
+
+    /// Returns a lazy view of the whole dataset, which can be sliced further without
+    /// reading any data until the resulting view is read (see [`LazyDataset`]).
+    pub fn lazy(&self) -> Result<LazyDataset> {
+        LazyDataset::new(self)
+    }
+
+    /// Writes all data from the array `arr` into the dataset, one chunk at a time.
+    ///
+    /// This is equivalent to [`write()`](Container::write), except that each chunk of the
+    /// dataset is written via a separate hyperslab selection, which bounds the amount of data
+    /// the library has to convert and buffer at once (e.g. when writing a large `f32` array
+    /// into an `f16` dataset). The shape of `arr` must match the shape of the dataset exactly;
+    /// datasets which are not chunked are written in a single call.
+    pub fn write_chunked<'b, A, T, D>(&self, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        let view = arr.into();
+        let chunk = match self.chunk() {
+            Some(chunk) => chunk,
+            None => return self.write(view),
+        };
+        let shape = self.shape();
+        ensure!(
+            view.shape() == shape.as_slice(),
+            "Shape mismatch: memory ({:?}) != destination ({:?})",
+            view.shape(),
+            shape
+        );
+        let grid: Vec<Ix> = shape.iter().zip(&chunk).map(|(&n, &c)| (n + c - 1) / c).collect();
+        for index in ndarray::indices(grid) {
+            let ranges: Vec<_> = (0..shape.len())
+                .map(|i| {
+                    let start = index[i] * chunk[i];
+                    start..(start + chunk[i]).min(shape[i])
+                })
+                .collect();
+            let block = view.slice_each_axis(|ax| ranges[ax.axis.index()].clone().into());
+            let selection: Vec<_> = ranges
+                .iter()
+                .map(|r| SliceOrIndex::SliceTo { start: r.start, step: 1, end: r.end, block: 1 })
+                .collect();
+            self.write_slice(block, Hyperslab::from(selection))?;
+        }
+        Ok(())
+    }
+
     /// Returns the pipeline of filters used in this dataset.
     pub fn filters(&self) -> Vec<Filter> {
         self.dcpl().map_or(Vec::default(), |pl| pl.filters())
     }
+
+    /// Returns the pipeline of filters used in this dataset along with their flags.
+    pub fn filter_pipeline(&self) -> Vec<PipelineFilter> {
+        self.dcpl().map_or(Vec::default(), |pl| pl.filter_pipeline())
+    }
+
+    /// Returns the number of bytes required to hold the variable-length payload of the
+    /// whole dataset when it is read into memory (excluding the fixed-size part of each
+    /// element, e.g. the `hvl_t` structs or string pointers).
+    ///
+    /// This can be used to estimate memory requirements before reading a dataset that
+    /// contains variable-length arrays or strings; for datasets without any variable-length
+    /// components, the returned size is zero.
+    pub fn vlen_buf_size(&self) -> Result<u64> {
+        h5lock!({
+            let mem_dtype = Datatype::from_descriptor(&self.dtype()?.to_descriptor()?)?;
+            let space = self.space()?;
+            let mut size: hsize_t = 0;
+            h5try!(H5Dvlen_get_buf_size(self.id(), mem_dtype.id(), space.id(), &mut size));
+            Ok(size as _)
+        })
+    }
+
+    #[cfg(feature = "mmap")]
+    /// Memory-maps the raw data of the dataset directly from the file and returns it as a
+    /// read-only array, so that it can be accessed without reading the whole dataset.
+    ///
+    /// The dataset must be contiguous (not chunked, compact or external), allocated in the file
+    /// and unfiltered, and its datatype must be identical to that of `T`, so that no conversion
+    /// (including byte order conversion) is required; variable-length types are not supported.
+    /// The file must be stored on disk using one of the single-file drivers (`sec2`, `stdio`
+    /// or `log`). An error is returned if any of these conditions is not satisfied, or if the
+    /// raw data is not aligned in the file as required by `T` (this can be ensured when creating
+    /// the file via `FileAccessBuilder::alignment()`).
+    ///
+    /// # Safety
+    ///
+    /// The mapped region of the file must not be modified while the returned array is alive,
+    /// neither via this crate (e.g. by writing to the dataset) nor by other processes.
+    pub unsafe fn mmap<T: H5Type>(&self) -> Result<MmapArray<T>> {
+        let (offset, shape) = h5lock!({
+            let layout = self.layout();
+            ensure!(
+                layout == Layout::Contiguous,
+                "Only contiguous datasets can be memory-mapped, got {:?} layout",
+                layout
+            );
+            let dcpl = self.dcpl()?;
+            ensure!(dcpl.external().is_empty(), "Datasets with external storage cannot be mapped");
+            ensure!(!dcpl.has_filters(), "Datasets with filters cannot be memory-mapped");
+            let descriptor = T::type_descriptor();
+            ensure!(!is_var_len(&descriptor), "Variable-length types cannot be memory-mapped");
+            ensure!(
+                self.dtype()? == Datatype::from_descriptor(&descriptor)?,
+                "Dataset type differs from the memory type {}, conversion required",
+                descriptor
+            );
+            let file = self.file()?;
+            let driver = file.fapl()?.driver();
+            ensure!(
+                matches!(driver, FileDriver::Sec2 | FileDriver::Stdio | FileDriver::Log),
+                "Memory-mapping is not supported with {:?} file driver",
+                driver
+            );
+            let offset = self.offset().ok_or("Dataset storage has not been allocated")?;
+            file.flush()?;
+            Ok((offset, self.shape()))
+        })?;
+        MmapArray::new(&self.filename(), offset, shape)
+    }
+}
+
+/// Buffered writer appending bytes to a resizable 1-D `u8` dataset.
+///
+/// The written bytes are collected in a buffer (its size is a multiple of the chunk size, at
+/// least 64 KiB) which is appended to the dataset when it's full; the dataset is extended
+/// as needed. Calling [`flush()`](std::io::Write::flush) writes out the buffer and flushes
+/// the file. If appending fails, the buffered bytes are kept (and still counted by
+/// [`len()`](ByteWriter::len)), so flushing can be retried. The buffer is also written out
+/// when the writer is dropped, but any errors (and the bytes that couldn't be written) are
+/// silently discarded in this case, so it's recommended to flush explicitly.
+#[derive(Debug)]
+pub struct ByteWriter {
+    ds: Dataset,
+    len: usize,
+    buf: Vec<u8>,
+}
+
+impl ByteWriter {
+    const MIN_BUF_SIZE: usize = 64 * 1024;
+
+    pub fn new(ds: &Dataset) -> Result<Self> {
+        let ds = ds.clone();
+        let file_dtype = ds.dtype()?;
+        let mem_dtype = Datatype::from_type::<u8>()?;
+        mem_dtype.ensure_convertible(&file_dtype, Conversion::NoOp)?;
+
+        let space = ds.space()?;
+        ensure!(space.ndim() == 1, "Only rank 1 datasets can be written via ByteWriter");
+        ensure!(space.is_resizable(), "Only resizable datasets can be written via ByteWriter");
+        let chunk = ds.chunk().map_or(1, |chunk| chunk[0].max(1));
+        let capacity = (Self::MIN_BUF_SIZE + chunk - 1) / chunk * chunk;
+        Ok(Self { len: space.size(), ds, buf: Vec::with_capacity(capacity) })
+    }
+
+    /// Returns the number of bytes written so far, including the existing contents of
+    /// the dataset and the bytes that are still buffered.
+    pub fn len(&self) -> usize {
+        self.len + self.buf.len()
+    }
+
+    /// Returns `true` if the dataset is (and will be, once flushed) empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<()> {
+        if !data.is_empty() {
+            let new_len = self.len + data.len();
+            self.ds.resize(new_len)?;
+            self.ds.write_slice(data, self.len..new_len)?;
+            self.len = new_len;
+        }
+        Ok(())
+    }
+
+    fn flush_buf(&mut self) -> Result<()> {
+        let buf = std::mem::take(&mut self.buf);
+        let res = self.append(&buf);
+        self.buf = buf;
+        // the buffered bytes have already been reported as written, so they're only
+        // discarded once appended; otherwise they're kept so that flushing can be retried
+        if res.is_ok() {
+            self.buf.clear();
+        }
+        res
+    }
+}
+
+impl io::Write for ByteWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + data.len() > self.buf.capacity() {
+            self.flush_buf()?;
+        }
+        if data.len() >= self.buf.capacity() {
+            // large writes bypass the buffer
+            self.append(data)?;
+        } else {
+            self.buf.extend_from_slice(data);
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.ds.file()?.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for ByteWriter {
+    fn drop(&mut self) {
+        let _ = self.flush_buf();
+    }
+}
+
+#[cfg(feature = "mmap")]
+fn is_var_len(descriptor: &TypeDescriptor) -> bool {
+    match descriptor {
+        TypeDescriptor::VarLenArray(_)
+        | TypeDescriptor::VarLenAscii
+        | TypeDescriptor::VarLenUnicode => true,
+        TypeDescriptor::FixedArray(ty, _) => is_var_len(ty),
+        TypeDescriptor::Compound(tp) => tp.fields.iter().any(|f| is_var_len(&f.ty)),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "mmap")]
+/// A read-only memory-mapped view of the raw data of a contiguous dataset.
+///
+/// See [`Dataset::mmap()`] for details; the file mapping is released when this is dropped.
+pub struct MmapArray<T> {
+    mmap: Mmap,
+    shape: Vec<Ix>,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "mmap")]
+impl<T> MmapArray<T> {
+    unsafe fn new(filename: &str, offset: u64, shape: Vec<Ix>) -> Result<Self> {
+        let len = shape.iter().product::<usize>() * mem::size_of::<T>();
+        let file = fs::File::open(filename)
+            .map_err(|e| format!("Failed to open file {:?} for mapping: {}", filename, e))?;
+        let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+        ensure!(offset + len as u64 <= file_len, "Dataset raw data extends past the end of file");
+        let mmap = MmapOptions::new()
+            .offset(offset)
+            .len(len)
+            .map(&file)
+            .map_err(|e| format!("Failed to memory-map file {:?}: {}", filename, e))?;
+        ensure!(
+            mmap.as_ptr() as usize % mem::align_of::<T>() == 0,
+            "Dataset raw data at offset {} is not aligned for the memory type",
+            offset
+        );
+        Ok(Self { mmap, shape, _marker: PhantomData })
+    }
+
+    /// Returns the shape of the mapped array.
+    pub fn shape(&self) -> &[Ix] {
+        &self.shape
+    }
+
+    /// Returns the mapped data as an array view.
+    pub fn view(&self) -> ArrayViewD<'_, T> {
+        let len = self.shape.iter().product();
+        let data = unsafe { slice::from_raw_parts(self.mmap.as_ptr().cast::<T>(), len) };
+        ArrayView::from_shape(IxDyn(&self.shape), data).unwrap()
+    }
 }
 
 pub struct Maybe<T>(Option<T>);
@@ -257,6 +630,8 @@ impl DatasetBuilder {
         self.with_data_as::<A, T, D>(data, &T::type_descriptor())
     }
 
+    /// Like `with_data()`, but the dataset is created with the given file datatype;
+    /// the data is then converted by HDF5 on write (e.g. storing `f64` data as `f32`).
     pub fn with_data_as<'d, A, T, D>(
         self, data: A, type_desc: &TypeDescriptor,
     ) -> DatasetBuilderData<'d, T, D>
@@ -335,10 +710,6 @@ where
     }
 
     pub fn create<'n, N: Into<Maybe<&'n str>>>(&self, name: N) -> Result<Dataset> {
-        ensure!(
-            self.data.is_standard_layout(),
-            "input array is not in standard layout or is not contiguous"
-        ); // TODO: relax this when it's supported in the writer
         let extents = Extents::from(self.data.shape());
         let name = name.into().into();
         h5lock!({
@@ -408,6 +779,7 @@ struct DatasetBuilderInner {
     dcpl_builder: DatasetCreateBuilder,
     lcpl_builder: LinkCreateBuilder,
     packed: bool,
+    byte_order: Option<ByteOrder>,
     chunk: Option<Chunk>,
 }
 
@@ -428,6 +800,7 @@ impl DatasetBuilderInner {
             dcpl_builder: dcpl,
             lcpl_builder: lcpl,
             packed: false,
+            byte_order: None,
             chunk: None,
         }
     }
@@ -436,6 +809,10 @@ impl DatasetBuilderInner {
         self.packed = packed;
     }
 
+    pub fn byte_order(&mut self, order: ByteOrder) {
+        self.byte_order = Some(order);
+    }
+
     fn build_dapl(&self) -> Result<DatasetAccess> {
         let mut dapl = match &self.dapl_base {
             Some(dapl) => dapl.clone(),
@@ -450,6 +827,16 @@ impl DatasetBuilderInner {
         } else {
             return Ok(None);
         };
+        #[cfg(feature = "1.10.0")]
+        {
+            // virtual datasets have their own layout which can't be combined with chunking,
+            // so they are never chunked automatically, even if resizable
+            if self.dcpl_builder.has_virtual_map() {
+                let chunked = self.chunk.as_ref().map_or(false, |chunk| *chunk != Chunk::None);
+                ensure!(!chunked, "Chunking cannot be enabled for virtual datasets");
+                return Ok(None);
+            }
+        }
         let has_filters = self.dcpl_builder.has_filters()
             || self.dcpl_base.as_ref().map_or(false, DatasetCreate::has_filters);
         let chunking_required = has_filters || extents.is_resizable();
@@ -493,7 +880,11 @@ impl DatasetBuilderInner {
         let mut dcpl_builder = self.dcpl_builder.clone();
         if let Some(chunk) = self.compute_chunk_shape(dtype, extents)? {
             dcpl_builder.chunk(chunk);
-            if !dcpl_builder.has_fill_time() {
+            // don't override the fill time if it has been set in the base property list
+            let base_fill_time = self.dcpl_base.as_ref().map(DatasetCreate::fill_time);
+            if !dcpl_builder.has_fill_time()
+                && base_fill_time.map_or(true, |t| t == FillTime::IfSet)
+            {
                 // prevent resize glitch (borrowed from h5py)
                 dcpl_builder.fill_time(FillTime::Alloc);
             }
@@ -531,6 +922,17 @@ impl DatasetBuilderInner {
         // construct in-file type descriptor; convert to packed representation if needed
         let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
         let dtype = Datatype::from_descriptor(&desc)?;
+        if let Some(order) = self.byte_order {
+            // the data is converted to the file byte order by the library when written
+            let order = match order {
+                ByteOrder::LittleEndian => H5T_order_t::H5T_ORDER_LE,
+                ByteOrder::BigEndian => H5T_order_t::H5T_ORDER_BE,
+                _ => {
+                    fail!("Only little-endian or big-endian byte order can be set, got {:?}", order)
+                }
+            };
+            h5try!(H5Tset_order(dtype.id(), order));
+        }
 
         // construct DAPL and DCPL, validate filters
         let dapl = self.build_dapl()?;
@@ -655,6 +1057,10 @@ impl DatasetBuilderInner {
         self.with_dcpl(|pl| pl.set_filters(filters));
     }
 
+    pub fn filter_pipeline(&mut self, pipeline: &[PipelineFilter]) {
+        self.with_dcpl(|pl| pl.filter_pipeline(pipeline));
+    }
+
     pub fn deflate(&mut self, level: u8) {
         self.with_dcpl(|pl| pl.deflate(level));
     }
@@ -815,6 +1221,11 @@ impl DatasetBuilderInner {
         self.with_dcpl(|pl| pl.attr_creation_order(attr_creation_order));
     }
 
+    #[cfg(feature = "1.10.5")]
+    pub fn dset_no_attrs_hint(&mut self, minimize: bool) {
+        self.with_dcpl(|pl| pl.dset_no_attrs_hint(minimize));
+    }
+
     ////////////////////
     // LinkCreate     //
     ////////////////////
@@ -941,6 +1352,7 @@ macro_rules! impl_builder {
 macro_rules! impl_builder_methods {
     () => {
         impl_builder!(*: packed(packed: bool));
+        impl_builder!(*: byte_order(order: ByteOrder));
 
         impl_builder!(DatasetAccess: access/dapl);
 
@@ -956,6 +1368,7 @@ macro_rules! impl_builder_methods {
         impl_builder!(DatasetCreate: create/dcpl);
 
         impl_builder!(DatasetCreate: set_filters(filters: &[Filter]));
+        impl_builder!(DatasetCreate: filter_pipeline(pipeline: &[PipelineFilter]));
         impl_builder!(DatasetCreate: deflate(level: u8));
         impl_builder!(DatasetCreate: shuffle());
         impl_builder!(DatasetCreate: fletcher32());
@@ -1016,6 +1429,7 @@ macro_rules! impl_builder_methods {
         impl_builder!(DatasetCreate: obj_track_times(track_times: bool));
         impl_builder!(DatasetCreate: attr_phase_change(max_compact: u32, min_dense: u32));
         impl_builder!(DatasetCreate: attr_creation_order(attr_creation_order: AttrCreationOrder));
+        impl_builder!(#[cfg(feature = "1.10.5")] DatasetCreate: dset_no_attrs_hint(minimize: bool));
 
         impl_builder!(LinkCreate: link_create/lcpl);
 
@@ -1047,14 +1461,15 @@ impl<'d, T2: H5Type, D2: ndarray::Dimension> DatasetBuilderData<'d, T2, D2> {
 #[cfg(test)]
 mod tests {
     use super::{compute_chunk_shape, DatasetBuilder};
-    use crate::filters::Filter;
+    use crate::filters::{deflate_available, Filter};
     use crate::test::with_tmp_file;
     use crate::{Extent, Result, SimpleExtents};
 
     #[cfg(feature = "blosc")]
     use crate::filters::{Blosc, BloscShuffle};
 
-    use ndarray::Array2;
+    use hdf5_types::VarLenArray;
+    use ndarray::{Array1, Array2};
 
     #[allow(dead_code)]
     fn check_filter(func: impl Fn(DatasetBuilder) -> DatasetBuilder, flt: Filter) {
@@ -1070,6 +1485,49 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn test_filter_pipeline_flags() {
+        use crate::filters::FilterPipelineBuilder;
+        use crate::plist::DatasetCreateBuilder;
+
+        let mut builder = FilterPipelineBuilder::new();
+        builder.mandatory(Filter::shuffle()).optional(Filter::nbit());
+        if deflate_available() {
+            builder.optional(Filter::deflate(4));
+        }
+        builder.optional(Filter::fletcher32());
+        let pipeline = builder.finish();
+
+        with_tmp_file(|file| {
+            let arr = Array2::<u32>::from_shape_fn((100, 20), |(i, j)| (i * j) as _);
+            let ds = file
+                .new_dataset_builder()
+                .filter_pipeline(&pipeline)
+                .with_data(&arr)
+                .create("pipeline")
+                .unwrap();
+            assert_eq!(ds.filter_pipeline(), pipeline);
+            let filters = pipeline.iter().map(|f| f.filter.clone()).collect::<Vec<_>>();
+            assert_eq!(ds.filters(), filters);
+            assert_eq!(ds.read_2d::<u32>().unwrap(), arr);
+
+            // the flags are preserved when copying the property list
+            let dcpl = DatasetCreateBuilder::from_plist(&ds.dcpl().unwrap()).unwrap();
+            assert_eq!(dcpl.finish().unwrap().filter_pipeline(), pipeline);
+
+            // filters added otherwise use the default flags (optional except fletcher32)
+            let ds = file
+                .new_dataset::<u32>()
+                .shuffle()
+                .fletcher32()
+                .shape(10)
+                .create("default")
+                .unwrap();
+            let flags = ds.filter_pipeline().iter().map(|f| f.optional).collect::<Vec<_>>();
+            assert_eq!(flags, vec![true, false]);
+        })
+    }
+
     #[test]
     #[cfg(feature = "blosc")]
     fn test_blosc() {
@@ -1082,6 +1540,420 @@ mod tests {
         check_filter(|d| d.lzf(), Filter::LZF);
     }
 
+    #[test]
+    fn test_vlen_buf_size() {
+        with_tmp_file(|file| {
+            let arr = Array1::from(vec![
+                VarLenArray::from_slice(&[1_i32]),
+                VarLenArray::from_slice(&[2, 3]),
+                VarLenArray::from_slice(&[]),
+                VarLenArray::from_slice(&[4, 5, 6]),
+            ]);
+            let ds = file.new_dataset_builder().with_data(&arr).create("vlen").unwrap();
+            assert_eq!(ds.vlen_buf_size().unwrap(), 6 * 4);
+
+            let ds = file.new_dataset::<i32>().shape(10).create("fixed").unwrap();
+            assert_eq!(ds.vlen_buf_size().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    fn test_with_data_as() {
+        use crate::types::{FloatSize, TypeDescriptor};
+        with_tmp_file(|file| {
+            let arr = Array1::from(vec![0.5_f64, -1.25, 1e10, 3.0]);
+            let ds = file
+                .new_dataset_builder()
+                .with_data_as(&arr, &TypeDescriptor::Float(FloatSize::U4))
+                .create("f32")
+                .unwrap();
+            assert_eq!(ds.dtype().unwrap().size(), 4);
+            assert_eq!(ds.read_1d::<f32>().unwrap(), arr.mapv(|x| x as f32));
+            assert_eq!(ds.read_1d::<f64>().unwrap(), arr);
+
+            let res = file
+                .new_dataset_builder()
+                .with_data_as(&arr, &TypeDescriptor::Float(FloatSize::U4))
+                .no_convert()
+                .create("noconv");
+            assert!(res.is_err());
+        })
+    }
+
+    #[test]
+    fn test_shape_helpers() {
+        use crate::Extents;
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f64>().create("scalar").unwrap();
+            assert!(ds.is_scalar());
+            assert_eq!((ds.ndim(), ds.shape(), ds.maxdims(), ds.size()), (0, vec![], vec![], 1));
+
+            let ds = file.new_dataset::<f64>().shape(7).create("1d").unwrap();
+            assert!(!ds.is_scalar() && !ds.is_resizable());
+            assert_eq!((ds.ndim(), ds.shape(), ds.size()), (1, vec![7], 7));
+            assert_eq!(ds.maxdims(), vec![Some(7)]);
+
+            let ds = file.new_dataset::<f64>().shape((3.., 4..=10)).create("2d").unwrap();
+            assert!(!ds.is_scalar() && ds.is_resizable());
+            assert_eq!(ds.ndim(), 2);
+            assert_eq!(ds.shape(), vec![3, 4]);
+            assert_eq!(ds.maxdims(), vec![None, Some(10)]);
+            assert_eq!(ds.size(), 12);
+
+            let ds = file.new_dataset::<f64>().shape(Extents::Null).create("null").unwrap();
+            assert!(!ds.is_scalar());
+            assert_eq!((ds.ndim(), ds.shape(), ds.maxdims(), ds.size()), (0, vec![], vec![], 0));
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.0")]
+    fn test_flush_refresh() {
+        use crate::hl::plist::file_access::LogFlags;
+        use crate::test::with_tmp_path;
+        use crate::{File, FileIntent};
+        use ndarray::{arr1, s};
+
+        with_tmp_path(|path| {
+            let file = File::with_options().with_fapl(|p| p.libver_latest()).create(&path).unwrap();
+            let ds = file.new_dataset::<i32>().chunk(4).shape(0..).create("data").unwrap();
+            file.start_swmr_write().unwrap();
+            // a different driver (logging nothing) is needed for the reader to get its own
+            // view of the file, otherwise it would share the writer's metadata cache
+            let reader_file = File::with_options()
+                .with_fapl(|p| p.log_options(None, LogFlags::empty(), 0))
+                .open_swmr(&path)
+                .unwrap();
+            assert_eq!(reader_file.intent(), FileIntent::SwmrRead);
+            let reader = reader_file.dataset("data").unwrap();
+            assert_eq!(reader.shape(), vec![0]);
+            for i in 1..=3 {
+                let chunk = arr1(&[i as i32; 4]);
+                ds.resize(4 * i).unwrap();
+                ds.write_slice(&chunk, s![4 * (i - 1)..]).unwrap();
+                ds.flush().unwrap();
+                // the reader's extent is stale until the dataset is refreshed
+                assert_eq!(reader.shape(), vec![4 * (i - 1)]);
+                reader.refresh().unwrap();
+                assert_eq!(reader.shape(), vec![4 * i]);
+                assert_eq!(reader.read_slice_1d::<i32, _>(s![4 * (i - 1)..]).unwrap(), chunk);
+            }
+        })
+    }
+
+    #[test]
+    fn test_fill_region() {
+        use ndarray::{s, Array2};
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((4, 5), |(i, j)| (i * 5 + j) as i32);
+            let ds = file.new_dataset_builder().fill_value(-1).with_data(&arr).create("a").unwrap();
+            ds.fill_region(s![1..3, 2..]).unwrap();
+            let mut expected = arr.clone();
+            expected.slice_mut(s![1..3, 2..]).fill(-1);
+            assert_eq!(ds.read_2d::<i32>().unwrap(), expected);
+
+            let ds = file.new_dataset::<u8>().shape(3).create("b").unwrap();
+            ds.write(&vec![1_u8, 2, 3]).unwrap();
+            ds.fill_region(s![..2]).unwrap();
+            assert_eq!(ds.read_raw::<u8>().unwrap(), vec![0, 0, 3]);
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.5")]
+    fn test_dset_no_attrs_hint() {
+        use crate::test::with_tmp_path;
+        use crate::File;
+
+        let file_size = |minimize: bool| {
+            with_tmp_path(|path| {
+                let file = File::create(&path).unwrap();
+                for i in 0..100 {
+                    let name = format!("ds{}", i);
+                    let builder = file.new_dataset::<u8>().dset_no_attrs_hint(minimize);
+                    builder.shape(4).create(name.as_str()).unwrap();
+                }
+                file.close().unwrap();
+                std::fs::metadata(&path).unwrap().len()
+            })
+        };
+        assert!(file_size(true) < file_size(false));
+    }
+
+    #[test]
+    fn test_edc_check() {
+        use crate::test::with_tmp_path;
+        use crate::File;
+
+        let data = vec![0xdead_beef_u32; 256];
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset_builder().fletcher32().chunk(256).with_data(&data);
+            ds.create("data").unwrap();
+            file.close().unwrap();
+
+            // corrupt a single byte in the (uncompressed) chunk
+            let mut bytes = std::fs::read(&path).unwrap();
+            let pattern = 0xdead_beef_u32.to_ne_bytes();
+            let pos = bytes.windows(4).position(|w| w == pattern).unwrap();
+            bytes[pos] ^= 0xff;
+            std::fs::write(&path, bytes).unwrap();
+
+            let file = File::open(&path).unwrap();
+            let ds = file.dataset("data").unwrap();
+            assert!(ds.read_raw::<u32>().is_err());
+            assert!(ds.as_reader().edc_check(true).read_raw::<u32>().is_err());
+            let out = ds.as_reader().edc_check(false).read_raw::<u32>().unwrap();
+            assert_ne!(out[0], data[0]);
+            assert_eq!(out[1..], data[1..]);
+        })
+    }
+
+    #[test]
+    fn test_offset() {
+        use crate::test::with_tmp_path;
+        use crate::File;
+
+        let data: Vec<f32> = (0..1000).map(|i| i as f32 * 0.5).collect();
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.new_dataset_builder().with_data(&data).create("contiguous").unwrap();
+            file.new_dataset_builder().chunk(100).with_data(&data).create("chunked").unwrap();
+            file.new_dataset::<f32>().shape(10).create("unallocated").unwrap();
+            assert!(file.dataset("chunked").unwrap().offset().is_none());
+            assert!(file.dataset("unallocated").unwrap().offset().is_none());
+            let offset = file.dataset("contiguous").unwrap().offset().unwrap() as usize;
+            file.close().unwrap();
+
+            // the raw data is stored at the reported offset and can be accessed directly
+            let bytes = std::fs::read(&path).unwrap();
+            assert!(offset + data.len() * 4 <= bytes.len());
+            let raw: Vec<f32> = bytes[offset..offset + data.len() * 4]
+                .chunks(4)
+                .map(|c| f32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            assert_eq!(raw, data);
+        })
+    }
+
+    #[test]
+    fn test_chunk_shape() {
+        use crate::plist::dataset_create::Layout;
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u16>().chunk((10, 3)).shape((95, 7)).create("a").unwrap();
+            assert_eq!(ds.chunk(), Some(vec![10, 3]));
+            assert!(ds.is_chunked());
+            let ds = file.new_dataset::<u16>().shape((95, 7)).create("b").unwrap();
+            assert_eq!(ds.chunk(), None);
+            assert_eq!(ds.layout(), Layout::Contiguous);
+            let ds =
+                file.new_dataset::<u16>().layout(Layout::Compact).shape(4).create("c").unwrap();
+            assert_eq!(ds.chunk(), None);
+            // resizable datasets are always chunked, with the chunk shape chosen automatically
+            let ds = file.new_dataset::<u16>().shape((0.., 7)).create("d").unwrap();
+            let chunk = ds.chunk().unwrap();
+            assert_eq!(chunk.len(), 2);
+            assert!(chunk.iter().all(|&n| n > 0));
+        })
+    }
+
+    #[test]
+    fn test_sparse_chunks() {
+        use crate::plist::dataset_create::{AllocTime, FillTime};
+        use crate::plist::DatasetCreate;
+
+        with_tmp_file(|file| {
+            let chunk_bytes = 100 * 8;
+            let data = Array1::from_elem(100, 1.5_f64);
+            for &fill_time in &[FillTime::Never, FillTime::Alloc, FillTime::IfSet] {
+                let name = format!("{:?}", fill_time);
+                let ds = file
+                    .new_dataset::<f64>()
+                    .chunk(100)
+                    .alloc_time(Some(AllocTime::Incr))
+                    .fill_time(fill_time)
+                    .shape(100_000)
+                    .create(&*name)
+                    .unwrap();
+                assert_eq!(ds.dcpl().unwrap().fill_time(), fill_time);
+                assert_eq!(ds.storage_size(), 0);
+                for &i in &[0, 500, 99_900] {
+                    ds.write_slice(&data, i..i + 100).unwrap();
+                }
+                // only the written chunks (out of 1000) are allocated
+                assert_eq!(ds.storage_size(), 3 * chunk_bytes);
+                assert_eq!(ds.read_slice_1d::<f64, _>(500..600).unwrap(), data);
+            }
+
+            // the fill time from the base property list is not overridden
+            let dcpl = DatasetCreate::build().fill_time(FillTime::Never).finish().unwrap();
+            let ds = file
+                .new_dataset::<f64>()
+                .set_dcpl(&dcpl)
+                .chunk(100)
+                .shape(100_000)
+                .create("base")
+                .unwrap();
+            assert_eq!(ds.dcpl().unwrap().fill_time(), FillTime::Never);
+            ds.write_slice(&data, 0..100).unwrap();
+            assert_eq!(ds.storage_size(), chunk_bytes);
+            let ds = file.new_dataset::<f64>().chunk(100).shape(1000).create("default").unwrap();
+            assert_eq!(ds.dcpl().unwrap().fill_time(), FillTime::Alloc);
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.0")]
+    fn test_virtual_map_not_chunked() {
+        use crate::hl::plist::dataset_create::Layout;
+        use crate::test::with_tmp_dir;
+        use crate::File;
+
+        with_tmp_dir(|dir| {
+            let src = dir.join("src.h5");
+            File::create(&src).unwrap().new_dataset::<i32>().shape(10).create("data").unwrap();
+            let src = src.to_str().unwrap();
+            let file = File::create(dir.join("vds.h5")).unwrap();
+            let builder = || {
+                file.new_dataset::<i32>().shape(10..).virtual_map(src, "data", 10, .., 10.., 0..10)
+            };
+
+            // resizable datasets are chunked automatically, but virtual ones keep their layout
+            let ds = builder().create("vds").unwrap();
+            assert_eq!(ds.layout(), Layout::Virtual);
+            assert_eq!(ds.chunk(), None);
+            assert_eq!(ds.shape(), vec![10]);
+            assert_err!(
+                builder().chunk(5).create("chunked"),
+                "Chunking cannot be enabled for virtual datasets"
+            );
+            builder().no_chunk().create("unchunked").unwrap();
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.0")]
+    fn test_virtual_view() {
+        use crate::hl::plist::dataset_access::VirtualView;
+        use crate::hl::plist::dataset_create::Layout;
+        use crate::test::with_tmp_dir;
+        use crate::{DatasetAccess, File, SliceOrIndex};
+
+        with_tmp_dir(|dir| {
+            // one source file per block of 10 elements; the file for block 2 is missing
+            for &i in &[0, 1, 3] {
+                let file = File::create(dir.join(format!("src-{}.h5", i))).unwrap();
+                let data: Vec<i32> = (i * 10..(i + 1) * 10).collect();
+                file.new_dataset_builder().with_data(&data).create("data").unwrap();
+            }
+            let src = dir.join("src-%b.h5");
+            let blocks = SliceOrIndex::Unlimited { start: 0, step: 10, block: 10 };
+            let ds = File::create(dir.join("vds.h5"))
+                .unwrap()
+                .new_dataset::<i32>()
+                .shape(0..)
+                .fill_value(-1)
+                .virtual_map(src.to_str().unwrap(), "data", 10, .., 0.., blocks)
+                .create("vds")
+                .unwrap();
+            assert_eq!(ds.layout(), Layout::Virtual);
+            drop(ds);
+
+            let file = File::open(dir.join("vds.h5")).unwrap();
+            let open = |view, gap| {
+                let dapl = DatasetAccess::build()
+                    .virtual_view(view)
+                    .virtual_printf_gap(gap)
+                    .finish()
+                    .unwrap();
+                let ds = file.dataset_with_dapl("vds", &dapl).unwrap();
+                assert_eq!(ds.dapl().unwrap().virtual_view(), view);
+                ds
+            };
+            let head: Vec<i32> = (0..20).collect();
+
+            // without a gap, the extent ends at the first missing source in both modes
+            for &view in &[VirtualView::FirstMissing, VirtualView::LastAvailable] {
+                let ds = open(view, 0);
+                assert_eq!(ds.shape(), vec![20]);
+                assert_eq!(ds.read_raw::<i32>().unwrap(), head);
+            }
+
+            // with a gap, the last available source is found, and missing data is filled
+            let ds = open(VirtualView::FirstMissing, 1);
+            assert_eq!(ds.shape(), vec![20]);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), head);
+            let ds = open(VirtualView::LastAvailable, 1);
+            assert_eq!(ds.shape(), vec![40]);
+            let mut expected = head.clone();
+            expected.extend(vec![-1; 10]);
+            expected.extend(30..40);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), expected);
+            assert_eq!(ds.read_slice_1d::<i32, _>(15..35).unwrap().to_vec(), &expected[15..35]);
+        })
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        with_tmp_file(|file| {
+            let data = vec![7_u64; 10_000];
+            let ds = file.new_dataset_builder().with_data(&data).create("plain").unwrap();
+            assert_eq!(ds.logical_size(), 80_000);
+            assert_eq!(ds.compression_ratio(), 1.0);
+            let ds = file.new_dataset::<u64>().shape((0, 3)).create("empty").unwrap();
+            assert_eq!(ds.logical_size(), 0);
+            assert_eq!(ds.compression_ratio(), 1.0);
+
+            if deflate_available() {
+                let ds = file
+                    .new_dataset_builder()
+                    .deflate(9)
+                    .chunk(1000)
+                    .with_data(&data)
+                    .create("compressed")
+                    .unwrap();
+                assert_eq!(ds.logical_size(), 80_000);
+                assert!(ds.storage_size() < ds.logical_size());
+                assert!(ds.compression_ratio() > 10.0);
+            }
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_mmap() {
+        use crate::test::with_tmp_path;
+        use crate::File;
+        use ndarray::{s, Array2};
+
+        let arr = Array2::from_shape_fn((300, 40), |(i, j)| (i * 40 + j) as f64 / 7.0);
+        with_tmp_path(|path| {
+            // make sure the raw data is suitably aligned for mapping
+            let file =
+                File::with_options().with_fapl(|p| p.alignment(0, 64)).create(&path).unwrap();
+            let ds = file.new_dataset_builder().with_data(&arr).create("contiguous").unwrap();
+            file.new_dataset_builder().chunk((10, 10)).with_data(&arr).create("chunked").unwrap();
+            file.new_dataset::<f64>().shape(10).create("unallocated").unwrap();
+
+            let mapped = unsafe { ds.mmap::<f64>() }.unwrap();
+            assert_eq!(mapped.shape(), &[300, 40]);
+            let view = mapped.view().into_dimensionality::<ndarray::Ix2>().unwrap();
+            assert_eq!(view, arr);
+            assert_eq!(
+                view.slice(s![120..130, 5..9]),
+                ds.read_slice_2d::<f64, _>(s![120..130, 5..9]).unwrap()
+            );
+            assert_err!(unsafe { ds.mmap::<f32>() }, "conversion required");
+            assert_err!(unsafe { ds.mmap::<u64>() }, "conversion required");
+
+            let ds = file.dataset("chunked").unwrap();
+            assert_err!(unsafe { ds.mmap::<f64>() }, "Only contiguous datasets");
+            let ds = file.dataset("unallocated").unwrap();
+            assert_err!(unsafe { ds.mmap::<f64>() }, "has not been allocated");
+        })
+    }
+
     #[test]
     fn test_compute_chunk_shape() {
         let e = SimpleExtents::new(&[1, 1]);
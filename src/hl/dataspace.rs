@@ -8,13 +8,13 @@ use hdf5_sys::h5s::H5Sencode1;
 use hdf5_sys::h5s::H5Sencode2;
 
 use hdf5_sys::h5s::{
-    H5S_class_t, H5Scopy, H5Screate, H5Screate_simple, H5Sdecode, H5Sget_select_npoints,
-    H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims, H5Sget_simple_extent_npoints,
-    H5Sget_simple_extent_type, H5Sselect_valid, H5S_UNLIMITED,
+    H5S_class_t, H5Scopy, H5Screate, H5Screate_simple, H5Sdecode, H5Sget_select_bounds,
+    H5Sget_select_npoints, H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims,
+    H5Sget_simple_extent_npoints, H5Sget_simple_extent_type, H5Sselect_valid, H5S_UNLIMITED,
 };
 
 use crate::hl::extents::{Extent, Extents, Ix};
-use crate::hl::selection::RawSelection;
+use crate::hl::selection::{set_hyperslab_union, RawHyperslab, RawSelection};
 use crate::internal_prelude::*;
 
 /// Represents the HDF5 dataspace object.
@@ -128,6 +128,8 @@ impl Dataspace {
         }
     }
 
+    /// Serializes the dataspace into a binary buffer, including its extents and the
+    /// current selection (of any kind, e.g. a union of several hyperslabs).
     #[allow(deprecated)]
     pub fn encode(&self) -> Result<Vec<u8>> {
         cfg_if::cfg_if! {
@@ -152,6 +154,8 @@ impl Dataspace {
         }
     }
 
+    /// Recreates a dataspace (along with its selection) from a buffer produced
+    /// by [`Dataspace::encode()`].
     pub fn decode<T>(buf: T) -> Result<Self>
     where
         T: AsRef<[u8]>,
@@ -188,6 +192,22 @@ impl Dataspace {
         h5call!(H5Sget_select_npoints(self.id())).ok().map_or(0, |x| x as _)
     }
 
+    /// Returns the bounding box of the current selection as a pair of its first and last
+    /// (inclusive) corners, or `None` if nothing is selected or the dataspace is not simple.
+    pub fn select_bounds(&self) -> Option<(Vec<Ix>, Vec<Ix>)> {
+        if !self.is_simple() || self.selection_size() == 0 {
+            return None;
+        }
+        let ndim = self.ndim();
+        let mut start = vec![0; ndim];
+        let mut end = vec![0; ndim];
+        h5call!(H5Sget_select_bounds(self.id(), start.as_mut_ptr(), end.as_mut_ptr())).ok()?;
+        Some((
+            start.into_iter().map(|x| x as _).collect(),
+            end.into_iter().map(|x| x as _).collect(),
+        ))
+    }
+
     #[doc(hidden)]
     pub fn select_raw<S: Into<RawSelection>>(&self, raw_sel: S) -> Result<Self> {
         let raw_sel = raw_sel.into();
@@ -199,6 +219,16 @@ impl Dataspace {
         })
     }
 
+    /// Returns a copy of the dataspace with the union of the given hyperslabs selected.
+    pub(crate) fn select_hyperslab_union(&self, hypers: &[RawHyperslab]) -> Result<Self> {
+        sync(|| unsafe {
+            let space = self.copy();
+            set_hyperslab_union(space.id(), hypers)?;
+            ensure!(space.is_valid(), "Invalid selection, out of extents");
+            Ok(space)
+        })
+    }
+
     pub fn select<S: Into<Selection>>(&self, selection: S) -> Result<Self> {
         let raw_sel = selection.into().into_raw(&self.shape())?;
         self.select_raw(raw_sel)
@@ -294,6 +324,73 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dataspace_encode_selection() -> Result<()> {
+        use std::ptr;
+
+        use hdf5_sys::h5s::{H5S_seloper_t, H5Sselect_hyperslab};
+
+        let space = Dataspace::try_new((10, 20))?;
+        let ops =
+            [(H5S_seloper_t::H5S_SELECT_SET, [1, 2]), (H5S_seloper_t::H5S_SELECT_OR, [6, 10])];
+        for &(op, start) in &ops {
+            let start: [hsize_t; 2] = start;
+            let count: [hsize_t; 2] = [2, 3];
+            h5call!(H5Sselect_hyperslab(
+                space.id(),
+                op,
+                start.as_ptr(),
+                ptr::null(),
+                count.as_ptr(),
+                ptr::null()
+            ))?;
+        }
+        let decoded = Dataspace::decode(space.encode()?)?;
+        assert_eq!(decoded.shape(), vec![10, 20]);
+        assert_eq!(decoded.selection_size(), 12);
+        assert_eq!(decoded.select_bounds(), space.select_bounds());
+
+        let space = space.select((2..8, 3))?;
+        let decoded = Dataspace::decode(space.encode()?)?;
+        assert_eq!(decoded.selection_size(), 6);
+        assert_eq!(decoded.get_selection()?, space.get_selection()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dataspace_select_bounds() -> Result<()> {
+        use std::ptr;
+
+        use hdf5_sys::h5s::{H5S_seloper_t, H5Sselect_hyperslab};
+
+        use crate::hl::selection::RawSelection;
+
+        let space = Dataspace::try_new((10, 20))?;
+        assert_eq!(space.select_bounds(), Some((vec![0, 0], vec![9, 19])));
+        assert_eq!(space.select((2..5, 3))?.select_bounds(), Some((vec![2, 3], vec![4, 3])));
+        assert_eq!(space.select_raw(RawSelection::None)?.select_bounds(), None);
+        assert_eq!(Dataspace::try_new(())?.select_bounds(), None);
+
+        // a union of two disjoint hyperslabs
+        let ops =
+            [(H5S_seloper_t::H5S_SELECT_SET, [1, 2]), (H5S_seloper_t::H5S_SELECT_OR, [6, 10])];
+        for &(op, start) in &ops {
+            let start: [hsize_t; 2] = start;
+            let count: [hsize_t; 2] = [2, 3];
+            h5call!(H5Sselect_hyperslab(
+                space.id(),
+                op,
+                start.as_ptr(),
+                ptr::null(),
+                count.as_ptr(),
+                ptr::null()
+            ))?;
+        }
+        assert_eq!(space.selection_size(), 12);
+        assert_eq!(space.select_bounds(), Some((vec![1, 2], vec![7, 12])));
+        Ok(())
+    }
+
     #[test]
     fn test_dataspace_repr() -> Result<()> {
         assert_eq!(&format!("{:?}", Dataspace::try_new(Extents::Null)?), "<HDF5 dataspace: null>");
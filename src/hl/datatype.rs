@@ -2,14 +2,16 @@ use std::borrow::Borrow;
 use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::fmt::{self, Debug, Display};
 use std::ops::Deref;
+use std::ptr;
 
 use hdf5_sys::h5t::{
     H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_order_t, H5T_sign_t, H5T_str_t, H5Tarray_create2,
-    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind,
-    H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name,
-    H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_order,
-    H5Tget_sign, H5Tget_size, H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tset_cset,
-    H5Tset_size, H5Tset_strpad, H5Tvlen_create, H5T_VARIABLE,
+    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tdecode, H5Tencode, H5Tenum_create, H5Tenum_insert,
+    H5Tequal, H5Tfind, H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset,
+    H5Tget_member_index, H5Tget_member_name, H5Tget_member_offset, H5Tget_member_type,
+    H5Tget_member_value, H5Tget_nmembers, H5Tget_order, H5Tget_sign, H5Tget_size, H5Tget_super,
+    H5Tinsert, H5Tis_variable_str, H5Tset_cset, H5Tset_size, H5Tset_strpad, H5Tvlen_create,
+    H5T_VARIABLE,
 };
 use hdf5_types::{
     CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
@@ -153,7 +155,58 @@ impl From<H5T_order_t> for ByteOrder {
     }
 }
 
+/// Classification of a datatype, as reported by the HDF5 library.
+///
+/// Unlike [`TypeDescriptor`], this can describe any datatype (including ones which have no
+/// Rust equivalent) and only contains the top-level properties of the type.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum TypeClass {
+    Integer { signed: bool, size: usize },
+    Float { size: usize },
+    String { variable: bool, is_utf8: bool },
+    Compound,
+    Enum,
+    Array,
+    VarLen,
+    Reference,
+    Opaque,
+    Bitfield,
+    Time,
+}
+
 impl Datatype {
+    /// Get the class of the datatype.
+    pub fn type_class(&self) -> Result<TypeClass> {
+        h5lock!({
+            let id = self.id();
+            let size = H5Tget_size(id) as usize;
+            Ok(match H5Tget_class(id) {
+                H5T_class_t::H5T_INTEGER => {
+                    let signed = match H5Tget_sign(id) {
+                        H5T_sign_t::H5T_SGN_NONE => false,
+                        H5T_sign_t::H5T_SGN_2 => true,
+                        _ => fail!("Invalid sign of integer datatype"),
+                    };
+                    TypeClass::Integer { signed, size }
+                }
+                H5T_class_t::H5T_FLOAT => TypeClass::Float { size },
+                H5T_class_t::H5T_STRING => TypeClass::String {
+                    variable: h5try!(H5Tis_variable_str(id)) == 1,
+                    is_utf8: H5Tget_cset(id) == H5T_cset_t::H5T_CSET_UTF8,
+                },
+                H5T_class_t::H5T_COMPOUND => TypeClass::Compound,
+                H5T_class_t::H5T_ENUM => TypeClass::Enum,
+                H5T_class_t::H5T_ARRAY => TypeClass::Array,
+                H5T_class_t::H5T_VLEN => TypeClass::VarLen,
+                H5T_class_t::H5T_REFERENCE => TypeClass::Reference,
+                H5T_class_t::H5T_OPAQUE => TypeClass::Opaque,
+                H5T_class_t::H5T_BITFIELD => TypeClass::Bitfield,
+                H5T_class_t::H5T_TIME => TypeClass::Time,
+                class => fail!("Invalid datatype class: {:?}", class),
+            })
+        })
+    }
+
     /// Get the total size of the datatype in bytes.
     pub fn size(&self) -> usize {
         h5lock!(H5Tget_size(self.id())) as usize
@@ -196,6 +249,82 @@ impl Datatype {
         Self::from_type::<T>().ok().map_or(false, |dtype| &dtype == self)
     }
 
+    /// Serializes the datatype definition (including all nested member types) into a
+    /// binary buffer.
+    ///
+    /// The buffer can be stored or transmitted independently of the file, and turned back
+    /// into an equal datatype via [`Datatype::decode()`].
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        h5lock!({
+            let mut len: size_t = 0;
+            h5try!(H5Tencode(self.id(), ptr::null_mut(), &mut len));
+            let mut buf = vec![0_u8; len];
+            h5try!(H5Tencode(self.id(), buf.as_mut_ptr().cast(), &mut len));
+            Ok(buf)
+        })
+    }
+
+    /// Recreates a datatype from a buffer produced by [`Datatype::encode()`].
+    pub fn decode<T>(buf: T) -> Result<Self>
+    where
+        T: AsRef<[u8]>,
+    {
+        let buf = buf.as_ref();
+        ensure!(!buf.is_empty(), "Cannot decode a datatype from an empty buffer");
+        h5lock!(Self::from_id(h5try!(H5Tdecode(buf.as_ptr().cast()))))
+    }
+
+    fn has_members(&self) -> bool {
+        matches!(
+            h5lock!(H5Tget_class(self.id())),
+            H5T_class_t::H5T_COMPOUND | H5T_class_t::H5T_ENUM
+        )
+    }
+
+    /// Returns the number of members (fields) of a compound or enum datatype,
+    /// or 0 for other datatypes.
+    pub fn num_members(&self) -> usize {
+        if self.has_members() {
+            h5lock!(H5Tget_nmembers(self.id())).max(0) as _
+        } else {
+            0
+        }
+    }
+
+    /// Returns the index of the member with the given name of a compound or enum datatype.
+    ///
+    /// Member indices range from 0 to `num_members() - 1`; `None` is returned if there is
+    /// no such member or if the datatype is neither compound nor enum.
+    pub fn member_index(&self, name: &str) -> Option<usize> {
+        let name = to_cstring(name).ok()?;
+        if !self.has_members() {
+            return None;
+        }
+        let index = h5lock!(H5Tget_member_index(self.id(), name.as_ptr()));
+        if index >= 0 {
+            Some(index as _)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name of the member with the given index of a compound or enum datatype
+    /// (see [`Datatype::member_index()`]).
+    pub fn member_name(&self, index: usize) -> Option<String> {
+        if index >= self.num_members() {
+            return None;
+        }
+        h5lock!({
+            let name = H5Tget_member_name(self.id(), index as _);
+            if name.is_null() {
+                return None;
+            }
+            let member_name = string_from_cstr(name);
+            h5_free_memory(name.cast());
+            Some(member_name)
+        })
+    }
+
     pub(crate) fn ensure_convertible(&self, dst: &Self, required: Conversion) -> Result<()> {
         // TODO: more detailed error messages after Debug/Display are implemented for Datatype
         if let Some(conv) = self.conv_path(dst) {
@@ -3,18 +3,43 @@ use std::mem;
 use std::ops::Deref;
 use std::path::Path;
 
+use bitflags::bitflags;
+
+#[cfg(feature = "1.10.0")]
+use hdf5_sys::h5f::{
+    H5F_sect_info_t, H5Fget_free_sections, H5Fstart_swmr_write, H5FD_MEM_DEFAULT,
+    H5F_ACC_SWMR_READ, H5F_ACC_SWMR_WRITE,
+};
 use hdf5_sys::h5f::{
     H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize,
-    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_DEFAULT,
-    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_SCOPE_LOCAL,
+    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_EXCL,
+    H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_OBJ_ALL, H5F_OBJ_ATTR, H5F_OBJ_DATASET,
+    H5F_OBJ_DATATYPE, H5F_OBJ_FILE, H5F_OBJ_GROUP, H5F_OBJ_LOCAL, H5F_SCOPE_LOCAL,
 };
 
+use crate::globals::{H5E_CANTLOCK, H5E_FILE, H5E_NOTFOUND};
 use crate::hl::plist::{
     file_access::{FileAccess, FileAccessBuilder},
     file_create::{FileCreate, FileCreateBuilder},
 };
 use crate::internal_prelude::*;
 
+bitflags! {
+    /// Types of open objects to look for in [`File::open_object_count`] and
+    /// [`File::open_object_ids`].
+    pub struct ObjectTypes: c_uint {
+        const FILE = H5F_OBJ_FILE;
+        const DATASET = H5F_OBJ_DATASET;
+        const GROUP = H5F_OBJ_GROUP;
+        const DATATYPE = H5F_OBJ_DATATYPE;
+        const ATTRIBUTE = H5F_OBJ_ATTR;
+        const ALL = H5F_OBJ_ALL;
+        /// Only consider objects opened through this file handle (rather than through any
+        /// handle of the same file).
+        const LOCAL = H5F_OBJ_LOCAL;
+    }
+}
+
 /// File opening mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OpenMode {
@@ -30,6 +55,52 @@ pub enum OpenMode {
     Append,
 }
 
+/// Access mode of an open file, as returned by [`File::intent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileIntent {
+    /// The file is open as read-only.
+    ReadOnly,
+    /// The file is open as read/write.
+    ReadWrite,
+    #[cfg(feature = "1.10.0")]
+    /// The file is open as read-only in single-writer/multiple-reader (SWMR) mode.
+    SwmrRead,
+    #[cfg(feature = "1.10.0")]
+    /// The file is open as read/write in SWMR mode (see [`File::start_swmr_write`]).
+    SwmrWrite,
+}
+
+impl FileIntent {
+    /// Returns true if the file can be written to.
+    pub fn is_writable(self) -> bool {
+        #[cfg(feature = "1.10.0")]
+        {
+            if self == Self::SwmrWrite {
+                return true;
+            }
+        }
+        self == Self::ReadWrite
+    }
+}
+
+impl From<c_uint> for FileIntent {
+    fn from(flags: c_uint) -> Self {
+        #[cfg(feature = "1.10.0")]
+        {
+            if flags & H5F_ACC_SWMR_WRITE != 0 {
+                return Self::SwmrWrite;
+            } else if flags & H5F_ACC_SWMR_READ != 0 {
+                return Self::SwmrRead;
+            }
+        }
+        if flags & H5F_ACC_RDWR != 0 {
+            Self::ReadWrite
+        } else {
+            Self::ReadOnly
+        }
+    }
+}
+
 /// HDF5 file object.
 #[repr(transparent)]
 #[derive(Clone)]
@@ -82,6 +153,13 @@ impl File {
         Self::open_as(filename, OpenMode::ReadWrite)
     }
 
+    #[cfg(feature = "1.10.0")]
+    /// Opens a file as read-only in single-writer/multiple-reader (SWMR) mode, file must
+    /// exist (see [`FileBuilder::open_swmr`]).
+    pub fn open_swmr<P: AsRef<Path>>(filename: P) -> Result<Self> {
+        FileBuilder::new().open_swmr(filename)
+    }
+
     /// Creates a file, truncates if exists.
     pub fn create<P: AsRef<Path>>(filename: P) -> Result<Self> {
         Self::open_as(filename, OpenMode::Create)
@@ -117,9 +195,35 @@ impl File {
         h5lock!(H5Fget_freespace(self.id())).max(0) as _
     }
 
+    #[cfg(feature = "1.10.0")]
+    /// Returns the free-space sections in the file as `(address, size)` pairs, in bytes
+    /// (or an empty vector if the file handle is invalid).
+    ///
+    /// Together with `free_space()`, this can help deciding whether the file should be
+    /// repacked to reclaim the unused space.
+    pub fn free_sections(&self) -> Vec<(u64, u64)> {
+        h5lock!({
+            let id = self.id();
+            let n = H5Fget_free_sections(id, H5FD_MEM_DEFAULT, 0, std::ptr::null_mut());
+            if n <= 0 {
+                return vec![];
+            }
+            let mut sections = vec![H5F_sect_info_t::default(); n as _];
+            let n = H5Fget_free_sections(id, H5FD_MEM_DEFAULT, n as _, sections.as_mut_ptr());
+            sections.truncate(n.max(0) as _);
+            sections.into_iter().map(|s| (s.addr as _, s.size as _)).collect()
+        })
+    }
+
+    /// Returns the access mode the file is open with (or read-only if the file handle
+    /// is invalid).
+    pub fn intent(&self) -> FileIntent {
+        h5get!(H5Fget_intent(self.id()): c_uint).map_or(FileIntent::ReadOnly, FileIntent::from)
+    }
+
     /// Returns true if the file was opened in a read-only mode.
     pub fn is_read_only(&self) -> bool {
-        h5get!(H5Fget_intent(self.id()): c_uint).unwrap_or(H5F_ACC_DEFAULT) != H5F_ACC_RDWR
+        !self.intent().is_writable()
     }
 
     /// Returns the userblock size in bytes (or 0 if the file handle is invalid).
@@ -132,22 +236,40 @@ impl File {
         h5call!(H5Fflush(self.id(), H5F_SCOPE_LOCAL)).and(Ok(()))
     }
 
-    /// Returns objects IDs of the contained objects. NOTE: these are borrowed references.
-    #[allow(unused)]
-    fn get_obj_ids(&self, types: c_uint) -> Vec<hid_t> {
+    #[cfg(feature = "1.10.0")]
+    /// Enables the single-writer/multiple-reader (SWMR) mode for the file.
+    ///
+    /// The file must be opened for writing with the latest library version bounds
+    /// (see `FileAccessBuilder::libver_latest()`). Once this is called, no new objects can
+    /// be created but the existing datasets can be extended; use `Dataset::flush()` to make
+    /// the changes visible to the readers and `Dataset::refresh()` on the reader side.
+    pub fn start_swmr_write(&self) -> Result<()> {
+        h5call!(H5Fstart_swmr_write(self.id())).and(Ok(()))
+    }
+
+    /// Returns the number of currently open objects of the given types in the file
+    /// (including the file handles themselves if `ObjectTypes::FILE` is requested).
+    ///
+    /// This is mostly useful for tracking down handles which prevent the file from closing.
+    pub fn open_object_count(&self, types: ObjectTypes) -> usize {
+        h5call!(H5Fget_obj_count(self.id(), types.bits())).map_or(0, |count| count as _)
+    }
+
+    /// Returns the identifiers of currently open objects of the given types in the file.
+    ///
+    /// NOTE: these are borrowed references which must not be closed.
+    pub fn open_object_ids(&self, types: ObjectTypes) -> Vec<hid_t> {
         h5lock!({
-            let count = h5call!(H5Fget_obj_count(self.id(), types)).unwrap_or(0) as size_t;
-            if count > 0 {
-                let mut ids: Vec<hid_t> = Vec::with_capacity(count as _);
-                if h5call!(H5Fget_obj_ids(self.id(), types, count, ids.as_mut_ptr())).is_ok() {
-                    unsafe {
-                        ids.set_len(count as _);
-                    }
-                    ids.retain(|id| *id != self.id());
-                    return ids;
-                }
+            let count = self.open_object_count(types);
+            let mut ids: Vec<hid_t> = vec![H5I_INVALID_HID; count];
+            if count == 0 {
+                return ids;
+            }
+            match h5call!(H5Fget_obj_ids(self.id(), types.bits(), count, ids.as_mut_ptr())) {
+                Ok(n) => ids.truncate(n as _),
+                Err(_) => ids.clear(),
             }
-            Vec::new()
+            ids
         })
     }
 
@@ -203,6 +325,33 @@ impl FileBuilder {
         self.open_as(filename, OpenMode::ReadWrite)
     }
 
+    #[cfg(feature = "1.10.0")]
+    /// Opens a file as read-only in single-writer/multiple-reader (SWMR) mode, file must
+    /// exist.
+    ///
+    /// The file must use the latest file format (see `FileAccessBuilder::libver_latest()`).
+    /// Datasets opened this way don't see the changes made by the writer (after it has
+    /// called [`File::start_swmr_write`]) until they are refreshed via `Dataset::refresh()`.
+    ///
+    /// Note that HDF5 shares the underlying file between all handles of the same file opened
+    /// within a process using the same file driver, so a SWMR reader in the same process as
+    /// the writer has to use a different driver to get a separate view of the file.
+    pub fn open_swmr<P: AsRef<Path>>(&self, filename: P) -> Result<File> {
+        let path = filename.as_ref();
+        let filename = to_cstring(
+            path.to_str().ok_or_else(|| format!("Invalid UTF-8 in file name: {:?}", path))?,
+        )?;
+        h5lock!({
+            let fapl = self.fapl.finish()?;
+            let flags = H5F_ACC_RDONLY | H5F_ACC_SWMR_READ;
+            let id = H5Fopen(filename.as_ptr(), flags, fapl.id());
+            if id < 0 {
+                push_open_failure_cause(path, false);
+            }
+            File::from_id(h5check(id)?)
+        })
+    }
+
     /// Creates a file, truncates if exists.
     pub fn create<P: AsRef<Path>>(&self, filename: P) -> Result<File> {
         self.open_as(filename, OpenMode::Create)
@@ -221,28 +370,40 @@ impl FileBuilder {
     /// Opens a file in a given mode.
     pub fn open_as<P: AsRef<Path>>(&self, filename: P, mode: OpenMode) -> Result<File> {
         let filename = filename.as_ref();
-        if mode == OpenMode::Append {
-            if let Ok(file) = self.open_as(filename, OpenMode::ReadWrite) {
-                return Ok(file);
+        let flags = match mode {
+            OpenMode::Read => H5F_ACC_RDONLY,
+            OpenMode::ReadWrite => H5F_ACC_RDWR,
+            OpenMode::Create => H5F_ACC_TRUNC,
+            OpenMode::CreateExcl => H5F_ACC_EXCL,
+            OpenMode::Append => {
+                if let Ok(file) = self.open_as(filename, OpenMode::ReadWrite) {
+                    return Ok(file);
+                }
+                // If the file can't be created exclusively, it may have been created
+                // concurrently (e.g. by another process) after the first attempt to open it,
+                // so try once more; if it exists but can't be opened (e.g. it's locked or
+                // not an HDF5 file), the error of opening it is the one worth reporting.
+                return self
+                    .open_as(filename, OpenMode::CreateExcl)
+                    .or_else(|_| self.open_as(filename, OpenMode::ReadWrite));
             }
-        }
+        };
+        let path = filename;
         let filename = to_cstring(
             filename
                 .to_str()
                 .ok_or_else(|| format!("Invalid UTF-8 in file name: {:?}", filename))?,
         )?;
-        let flags = match mode {
-            OpenMode::Read => H5F_ACC_RDONLY,
-            OpenMode::ReadWrite => H5F_ACC_RDWR,
-            OpenMode::Create => H5F_ACC_TRUNC,
-            OpenMode::CreateExcl | OpenMode::Append => H5F_ACC_EXCL,
-        };
         let fname_ptr = filename.as_ptr();
         h5lock!({
             let fapl = self.fapl.finish()?;
             match mode {
                 OpenMode::Read | OpenMode::ReadWrite => {
-                    File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
+                    let id = H5Fopen(fname_ptr, flags, fapl.id());
+                    if id < 0 {
+                        push_open_failure_cause(path, mode == OpenMode::ReadWrite);
+                    }
+                    File::from_id(h5check(id)?)
                 }
                 _ => {
                     let fcpl = self.fcpl.finish()?;
@@ -337,6 +498,39 @@ impl FileBuilder {
     }
 }
 
+/// Pushes the likely cause of a failure to open a file onto the error stack, so that
+/// `Error::open_error()` can report it (the library doesn't tell these cases apart).
+fn push_open_failure_cause(path: &Path, writable: bool) {
+    if !path.exists() {
+        h5err!("File not found", H5E_FILE, H5E_NOTFOUND);
+    } else if is_locked_elsewhere(path, writable) {
+        h5err!("File is locked", H5E_FILE, H5E_CANTLOCK);
+    }
+}
+
+/// Checks whether the file is locked in a way that conflicts with opening it (as done by
+/// the library if file locking is enabled).
+///
+/// Since 1.12.1, the library reports its locking failures with a dedicated error code.
+#[cfg(all(unix, feature = "1.10.0", not(feature = "1.12.1")))]
+fn is_locked_elsewhere(path: &Path, writable: bool) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+    let op = if writable { libc::LOCK_EX } else { libc::LOCK_SH } | libc::LOCK_NB;
+    // the lock, if acquired, is released when the file is closed
+    unsafe { libc::flock(file.as_raw_fd(), op) != 0 }
+    &&std::io::Error::last_os_error().raw_os_error() == Some(libc::EWOULDBLOCK)
+}
+
+#[cfg(not(all(unix, feature = "1.10.0", not(feature = "1.12.1"))))]
+fn is_locked_elsewhere(_path: &Path, _writable: bool) -> bool {
+    false
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::internal_prelude::*;
@@ -359,6 +553,29 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_intent() {
+        with_tmp_path(|path| {
+            assert_eq!(File::create(&path).unwrap().intent(), FileIntent::ReadWrite);
+            assert_eq!(File::open(&path).unwrap().intent(), FileIntent::ReadOnly);
+            assert_eq!(File::open_rw(&path).unwrap().intent(), FileIntent::ReadWrite);
+            assert!(FileIntent::ReadWrite.is_writable());
+            assert!(!FileIntent::ReadOnly.is_writable());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.0")]
+    pub fn test_intent_swmr() {
+        with_tmp_path(|path| {
+            let file = File::with_options().with_fapl(|p| p.libver_latest()).create(&path).unwrap();
+            assert_eq!(file.intent(), FileIntent::ReadWrite);
+            file.start_swmr_write().unwrap();
+            assert_eq!(file.intent(), FileIntent::SwmrWrite);
+            assert!(!file.is_read_only());
+        });
+    }
+
     #[test]
     pub fn test_unable_to_open() {
         with_tmp_dir(|dir| {
@@ -366,7 +583,7 @@ pub mod tests {
             assert_err_re!(File::open_rw(&dir), "unable to (?:synchronously )?open file");
             assert_err_re!(File::create_excl(&dir), "unable to (?:synchronously )?create file");
             assert_err_re!(File::create(&dir), "unable to (?:synchronously )?create file");
-            assert_err_re!(File::append(&dir), "unable to (?:synchronously )?create file");
+            assert_err_re!(File::append(&dir), "unable to (?:synchronously )?open file");
         });
         with_tmp_path(|path| {
             fs::File::create(&path).unwrap().write_all(b"foo").unwrap();
@@ -375,6 +592,51 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_open_error() {
+        with_tmp_dir(|dir| {
+            let err = File::open(dir.join("missing.h5")).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::NotFound));
+            let err = File::open_rw(dir.join("missing.h5")).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::NotFound));
+            // it exists, but it's not a file
+            let err = File::open(dir).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::CantOpen));
+        });
+        with_tmp_path(|path| {
+            fs::File::create(&path).unwrap().write_all(&[42; 4096]).unwrap();
+            let err = File::open(&path).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::BadSignature));
+        });
+        with_tmp_file(|file| {
+            let err = file.group("missing").unwrap_err();
+            assert_eq!(err.open_error(), None);
+        });
+        assert_eq!(Error::from("foo").open_error(), None);
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "1.10.0"))]
+    pub fn test_open_error_locked() {
+        use std::os::unix::io::AsRawFd;
+
+        if std::env::var("HDF5_USE_FILE_LOCKING").map_or(false, |v| v == "FALSE") {
+            return;
+        }
+        with_tmp_path(|path| {
+            File::create(&path).unwrap();
+            // hold a conflicting lock, as another process using the file would
+            let locked = fs::File::open(&path).unwrap();
+            assert_eq!(unsafe { libc::flock(locked.as_raw_fd(), libc::LOCK_EX) }, 0);
+            let err = File::open(&path).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::Locked));
+            let err = File::open_rw(&path).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::Locked));
+            drop(locked);
+            File::open(&path).unwrap();
+        })
+    }
+
     #[test]
     pub fn test_file_create() {
         with_tmp_path(|path| {
@@ -397,8 +659,22 @@ pub mod tests {
     #[test]
     pub fn test_file_append() {
         with_tmp_path(|path| {
-            File::append(&path).unwrap().create_group("foo").unwrap();
-            File::append(&path).unwrap().group("foo").unwrap();
+            let file = File::append(&path).unwrap();
+            assert!(!file.is_read_only());
+            file.create_group("foo").unwrap();
+            drop(file);
+            let file = File::append(&path).unwrap();
+            assert!(!file.is_read_only());
+            file.group("foo").unwrap();
+            file.create_group("bar").unwrap();
+            drop(file);
+            assert_eq!(File::open(&path).unwrap().member_names().unwrap(), vec!["bar", "foo"]);
+        });
+        with_tmp_path(|path| {
+            // the error of opening an existing file is reported, not "file exists"
+            fs::File::create(&path).unwrap().write_all(&[42; 4096]).unwrap();
+            let err = File::append(&path).unwrap_err();
+            assert_eq!(err.open_error(), Some(OpenError::BadSignature));
         });
     }
 
@@ -445,6 +721,122 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_free_space() {
+        with_tmp_file(|file| {
+            let arr = vec![0_u64; 1024];
+            for name in &["a", "b"] {
+                let ds = file.new_dataset::<u64>().no_chunk().shape(1024).create(*name).unwrap();
+                ds.write(&arr).unwrap();
+            }
+            let free_space = file.free_space();
+            file.unlink("a").unwrap();
+            assert!(file.free_space() >= free_space + 8 * 1024);
+            #[cfg(feature = "1.10.0")]
+            {
+                let sections = file.free_sections();
+                assert!(!sections.is_empty());
+                let total = sections.iter().map(|&(_, size)| size).sum::<u64>();
+                assert!(total >= 8 * 1024);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_size() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new().with_fcpl(|p| p.userblock(1024)).create(&path).unwrap();
+            let ds = file.new_dataset::<u8>().no_chunk().shape(10_000).create("foo").unwrap();
+            ds.write(&vec![7_u8; 10_000]).unwrap();
+            file.flush().unwrap();
+            // the file size includes the userblock and all metadata
+            assert_eq!(file.size(), fs::metadata(&path).unwrap().len());
+            assert!(file.size() >= 1024 + ds.storage_size());
+        })
+    }
+
+    #[test]
+    pub fn test_reproducible_without_track_times() {
+        with_tmp_dir(|dir| {
+            let create = |name: &str, track_times: bool| {
+                let path = dir.join(name);
+                let file = FileBuilder::new()
+                    .with_fcpl(|p| p.obj_track_times(track_times))
+                    .create(&path)
+                    .unwrap();
+                let group =
+                    file.new_group_builder().obj_track_times(track_times).create("group").unwrap();
+                group
+                    .new_dataset_builder()
+                    .obj_track_times(track_times)
+                    .with_data(&(0..100).collect::<Vec<i32>>())
+                    .create("data")
+                    .unwrap();
+                drop(group);
+                file.close().unwrap();
+                fs::read(&path).unwrap()
+            };
+            let first = create("first.h5", false);
+            assert_eq!(first, create("second.h5", false));
+            // the same contents with times recorded are stored differently
+            assert_ne!(first, create("tracked.h5", true));
+        })
+    }
+
+    #[test]
+    pub fn test_shared_messages() {
+        use crate::plist::file_create::{SharedMessageIndex, SharedMessageType};
+
+        type Row = (i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool, i8);
+        type Record = (Row, Row, Row, Row, Row, Row, Row, Row);
+
+        with_tmp_dir(|dir| {
+            let create = |name: &str, shared: bool| {
+                let path = dir.join(name);
+                let file = FileBuilder::new()
+                    .with_fcpl(|p| {
+                        if shared {
+                            p.shared_mesg_indexes(&[SharedMessageIndex {
+                                message_types: SharedMessageType::DATATYPE
+                                    | SharedMessageType::SIMPLE_DATASPACE,
+                                min_message_size: 0,
+                            }]);
+                        }
+                        p
+                    })
+                    .create(&path)
+                    .unwrap();
+                for i in 0..200 {
+                    file.new_dataset::<Record>().shape(10).create(&*format!("{}", i)).unwrap();
+                }
+                file.close().unwrap();
+                fs::metadata(&path).unwrap().len()
+            };
+            let plain = create("plain.h5", false);
+            let shared = create("shared.h5", true);
+            // the datatype message is stored once instead of in every object header
+            assert!(shared * 2 < plain, "shared: {}, plain: {}", shared, plain);
+        })
+    }
+
+    #[test]
+    pub fn test_btree_tuning() {
+        use crate::plist::file_create::SymbolTableInfo;
+
+        with_tmp_path(|path| {
+            FileBuilder::new()
+                .with_fcpl(|p| p.sym_k(64, 32).istore_k(128))
+                .create(&path)
+                .unwrap()
+                .create_group("foo")
+                .unwrap();
+            // the B-tree parameters are stored in the superblock
+            let fcpl = File::open(&path).unwrap().fcpl().unwrap();
+            assert_eq!(fcpl.sym_k(), SymbolTableInfo { tree_rank: 64, node_size: 32 });
+            assert_eq!(fcpl.istore_k(), 128);
+        })
+    }
+
     #[test]
     pub fn test_userblock() {
         with_tmp_file(|file| {
@@ -563,6 +955,123 @@ pub mod tests {
         });
     }
 
+    #[test]
+    #[cfg(all(feature = "1.10.1", not(feature = "have-parallel")))]
+    fn test_evict_on_close() {
+        use crate::hl::plist::file_access::FileCloseDegree;
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fapl(|fapl| fapl.evict_on_close(true).fclose_degree(FileCloseDegree::Strong))
+                .create(&path)
+                .unwrap();
+            let fapl = file.fapl().unwrap();
+            assert!(fapl.evict_on_close());
+            assert_eq!(fapl.fclose_degree(), FileCloseDegree::Strong);
+            for i in 0..10 {
+                let ds = file.new_dataset::<i32>().create(&*format!("ds{}", i)).unwrap();
+                ds.write_scalar(&i).unwrap();
+            }
+            drop(file);
+            let file = File::open(&path).unwrap();
+            assert_eq!(file.dataset("ds7").unwrap().read_scalar::<i32>().unwrap(), 7);
+        })
+    }
+
+    #[test]
+    pub fn test_alignment() {
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fapl(|fapl| fapl.alignment(1024, 4096))
+                .create(&path)
+                .unwrap();
+            let alignment = file.fapl().unwrap().alignment();
+            assert_eq!((alignment.threshold, alignment.alignment), (1024, 4096));
+            // only allocations at or above the threshold are aligned
+            for (name, size) in &[("a", 2000), ("b", 300), ("c", 5000)] {
+                let ds = file.new_dataset::<u8>().no_chunk().shape(*size).create(*name).unwrap();
+                ds.write(&vec![1_u8; *size]).unwrap();
+                if *size >= 1024 {
+                    assert_eq!(ds.offset().unwrap() % 4096, 0);
+                }
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_chunk_cache() {
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fapl(|fapl| fapl.chunk_cache(1009, 4 << 20, 0.25))
+                .create(&path)
+                .unwrap();
+            let cache = file.fapl().unwrap().chunk_cache();
+            assert_eq!((cache.nslots, cache.nbytes, cache.w0), (1009, 4 << 20, 0.25));
+
+            // the file-level chunk cache is the default for all datasets in the file
+            let ds = file.new_dataset::<u8>().chunk(100).shape(1000).create("a").unwrap();
+            let ds_override = file
+                .new_dataset::<u8>()
+                .chunk(100)
+                .chunk_cache(13, 1 << 20, 0.5)
+                .shape(1000)
+                .create("b")
+                .unwrap();
+            #[cfg(feature = "1.10.0")]
+            {
+                let cache = ds.dapl().unwrap().chunk_cache();
+                assert_eq!((cache.nslots, cache.nbytes, cache.w0), (1009, 4 << 20, 0.25));
+                let cache = ds_override.dapl().unwrap().chunk_cache();
+                assert_eq!((cache.nslots, cache.nbytes, cache.w0), (13, 1 << 20, 0.5));
+            }
+            ds.write(&vec![1_u8; 1000]).unwrap();
+            ds_override.write(&vec![2_u8; 1000]).unwrap();
+        })
+    }
+
+    #[test]
+    pub fn test_meta_block_size() {
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fapl(|fapl| fapl.meta_block_size(16384).small_data_block_size(8192))
+                .create(&path)
+                .unwrap();
+            let fapl = file.fapl().unwrap();
+            assert_eq!(fapl.meta_block_size(), 16384);
+            assert_eq!(fapl.small_data_block_size(), 8192);
+            for i in 0..500 {
+                let ds = file.new_dataset::<i32>().create(&*format!("ds{}", i)).unwrap();
+                ds.write_scalar(&i).unwrap();
+            }
+            file.flush().unwrap();
+            assert_eq!(file.dataset("ds123").unwrap().read_scalar::<i32>().unwrap(), 123);
+        });
+
+        // with small metadata blocks, the object headers of many small datasets are
+        // interleaved with their raw data, leaving unused fragments at the end of each block
+        let file_size = |meta_block_size: Option<u64>| {
+            with_tmp_path(|path| {
+                let mut builder = File::with_options();
+                if let Some(size) = meta_block_size {
+                    builder.fapl().meta_block_size(size);
+                }
+                let file = builder.create(&path).unwrap();
+                for i in 0..500 {
+                    let ds = file.new_dataset::<i32>().create(&*format!("ds{}", i)).unwrap();
+                    ds.write_scalar(&i).unwrap();
+                }
+                drop(file);
+                File::open(&path).unwrap().size()
+            })
+        };
+        let (default_size, large_size) = (file_size(None), file_size(Some(1 << 16)));
+        assert!(
+            large_size < default_size,
+            "{} (1 << 16) >= {} (default)",
+            large_size,
+            default_size
+        );
+    }
+
     #[test]
     pub fn test_close_automatic() {
         // File going out of scope should just close its own handle
@@ -576,6 +1085,30 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_open_objects() {
+        use super::ObjectTypes as OT;
+
+        with_tmp_file(|file| {
+            assert_eq!(file.open_object_count(OT::ALL), 1);
+            assert_eq!(file.open_object_ids(OT::FILE), vec![file.id()]);
+            assert!(file.open_object_ids(OT::DATASET).is_empty());
+
+            let group = file.create_group("g").unwrap();
+            let ds = group.new_dataset::<u8>().create("ds").unwrap();
+            let attr = ds.new_attr::<u8>().create("attr").unwrap();
+            assert_eq!(file.open_object_count(OT::ALL), 4);
+            assert_eq!(file.open_object_count(OT::GROUP | OT::DATASET), 2);
+            assert_eq!(file.open_object_ids(OT::DATASET), vec![ds.id()]);
+            assert_eq!(file.open_object_ids(OT::ATTRIBUTE), vec![attr.id()]);
+            assert_eq!(file.open_object_count(OT::DATATYPE), 0);
+            assert_eq!(file.open_object_count(OT::ALL | OT::LOCAL), 4);
+
+            drop((attr, ds, group));
+            assert_eq!(file.open_object_count(OT::ALL), 1);
+        })
+    }
+
     #[test]
     pub fn test_core_fd_non_filebacked() {
         with_tmp_path(|path| {
@@ -605,6 +1138,37 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(feature = "1.8.13")]
+    pub fn test_core_fd_write_tracking() {
+        use crate::file::FileDriver;
+
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.core_filebacked(true).write_tracking(4096))
+                .create(&path)
+                .unwrap();
+            match file.fapl().unwrap().driver() {
+                FileDriver::Core(drv) => {
+                    assert!(drv.filebacked);
+                    assert_eq!(drv.write_tracking, 4096);
+                }
+                driver => panic!("unexpected driver: {:?}", driver),
+            }
+            let data = (0..100_000).map(|i| (i % 256) as u8).collect::<Vec<_>>();
+            let ds = file.new_dataset_builder().with_data(&data).create("foo").unwrap();
+            file.flush().unwrap();
+            ds.write_slice(&[0_u8; 10], 5000..5010).unwrap();
+            drop(ds);
+            file.close().unwrap();
+
+            let mut expected = data;
+            expected[5000..5010].fill(0);
+            let ds = File::open(&path).unwrap().dataset("foo").unwrap();
+            assert_eq!(ds.read_raw::<u8>().unwrap(), expected);
+        })
+    }
+
     #[test]
     pub fn test_core_fd_existing_file() {
         with_tmp_path(|path| {
@@ -626,6 +1190,35 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_log_fd() {
+        use crate::plist::file_access::LogFlags;
+
+        with_tmp_dir(|dir| {
+            let path = dir.join("foo.h5");
+            let log = dir.join("foo.log");
+            let flags = LogFlags::TRUNCATE | LogFlags::LOC_IO | LogFlags::NUM_IO;
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.log_options(log.to_str(), flags, 0))
+                .create(&path)
+                .unwrap();
+            file.new_dataset_builder().with_data(&[1, 2, 3]).create("data").unwrap();
+            file.close().unwrap();
+            // the log file is flushed when the file is closed
+            let trace = fs::read_to_string(&log).unwrap();
+            assert!(trace.contains("Written"), "unexpected log: {}", trace);
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.log_options(log.to_str(), flags, 0))
+                .open(&path)
+                .unwrap();
+            assert_eq!(file.dataset("data").unwrap().read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+            file.close().unwrap();
+            let trace = fs::read_to_string(&log).unwrap();
+            assert!(trace.contains("Read"), "unexpected log: {}", trace);
+        })
+    }
+
     #[test]
     pub fn test_stdio_fd() {
         with_tmp_path(|path| {
@@ -639,6 +1232,29 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(all(feature = "have-direct", target_os = "linux"))]
+    pub fn test_direct_fd() {
+        with_tmp_path(|path| {
+            let builder = || {
+                let mut builder = FileBuilder::new();
+                builder.with_fapl(|p| p.direct_options(4096, 4096, 1 << 20));
+                builder
+            };
+            // file systems like tmpfs don't support O_DIRECT, nothing to test in this case
+            let file = match builder().create(&path) {
+                Ok(file) => file,
+                Err(_) => return,
+            };
+            assert!(matches!(file.fapl().unwrap().driver(), crate::file::FileDriver::Direct(_)));
+            let data = (0..10_000).map(|i| i as u32).collect::<Vec<_>>();
+            file.new_dataset_builder().with_data(&data[..]).create("data").unwrap();
+            file.close().unwrap();
+            let file = builder().open(&path).unwrap();
+            assert_eq!(file.dataset("data").unwrap().read_raw::<u32>().unwrap(), data);
+        })
+    }
+
     #[test]
     pub fn test_debug() {
         with_tmp_dir(|dir| {
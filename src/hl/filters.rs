@@ -2,8 +2,8 @@ use std::collections::HashMap;
 use std::ptr;
 
 use hdf5_sys::h5p::{
-    H5Pget_filter2, H5Pget_nfilters, H5Pset_deflate, H5Pset_filter, H5Pset_fletcher32, H5Pset_nbit,
-    H5Pset_scaleoffset, H5Pset_shuffle, H5Pset_szip,
+    H5Pget_filter2, H5Pget_filter_by_id2, H5Pget_nfilters, H5Pmodify_filter, H5Pset_deflate,
+    H5Pset_filter, H5Pset_fletcher32, H5Pset_nbit, H5Pset_scaleoffset, H5Pset_shuffle, H5Pset_szip,
 };
 use hdf5_sys::h5t::H5T_class_t;
 use hdf5_sys::h5z::{
@@ -22,6 +22,12 @@ use crate::internal_prelude::*;
 mod blosc;
 #[cfg(feature = "lzf")]
 mod lzf;
+mod user;
+
+pub use user::{
+    register_filter, register_filter_with_callbacks, unregister_filter, CanApplyFn, SetLocalFn,
+    MAX_USER_FILTERS,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SZip {
@@ -112,6 +118,56 @@ pub enum Filter {
     User(H5Z_filter_t, Vec<c_uint>),
 }
 
+/// A filter in the filter pipeline of a dataset along with its flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PipelineFilter {
+    /// The filter and its parameters.
+    pub filter: Filter,
+    /// Whether the filter is optional: if an optional filter fails when writing a chunk, the
+    /// chunk is stored without this filter applied (for instance, if the compressed data
+    /// would be larger than the original); if a mandatory filter fails, the write fails.
+    pub optional: bool,
+}
+
+/// Builder used to create a filter pipeline where filters are applied in the order they
+/// are added, each one being explicitly marked as either mandatory or optional.
+///
+/// The resulting pipeline can be passed to `DatasetBuilder::filter_pipeline()`. Note that the
+/// filters added via the other methods of `DatasetBuilder` (e.g. `deflate()`) use the flags
+/// set by the HDF5 library, which are optional for all filters except `fletcher32`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilterPipelineBuilder {
+    filters: Vec<PipelineFilter>,
+}
+
+impl FilterPipelineBuilder {
+    /// Creates a new empty filter pipeline builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a filter to the pipeline, marking it as either optional or mandatory.
+    pub fn push(&mut self, filter: Filter, optional: bool) -> &mut Self {
+        self.filters.push(PipelineFilter { filter, optional });
+        self
+    }
+
+    /// Appends a mandatory filter to the pipeline.
+    pub fn mandatory(&mut self, filter: Filter) -> &mut Self {
+        self.push(filter, false)
+    }
+
+    /// Appends an optional filter to the pipeline.
+    pub fn optional(&mut self, filter: Filter) -> &mut Self {
+        self.push(filter, true)
+    }
+
+    /// Returns the filters of the pipeline in the order they have been added.
+    pub fn finish(&self) -> Vec<PipelineFilter> {
+        self.filters.clone()
+    }
+}
+
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct FilterInfo {
     pub is_available: bool,
@@ -491,7 +547,43 @@ impl Filter {
         Ok(())
     }
 
+    /// Marks the filter (which must be already present in the pipeline) as either optional
+    /// or mandatory, keeping its parameters intact.
+    pub(crate) fn set_optional(&self, plist_id: hid_t, optional: bool) -> Result<()> {
+        let filter_id = self.id();
+        let mut cd_values: Vec<c_uint> = vec![0; 32];
+        h5lock!({
+            let mut flags: c_uint = 0;
+            let mut cd_nelmts: size_t;
+            loop {
+                cd_nelmts = cd_values.len() as _;
+                h5try!(H5Pget_filter_by_id2(
+                    plist_id,
+                    filter_id,
+                    &mut flags as *mut _,
+                    &mut cd_nelmts as *mut _,
+                    cd_values.as_mut_ptr(),
+                    0,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                ));
+                if (cd_nelmts as usize) <= cd_values.len() {
+                    break;
+                }
+                cd_values.resize(cd_nelmts as _, 0);
+            }
+            let flags =
+                if optional { flags | H5Z_FLAG_OPTIONAL } else { flags & !H5Z_FLAG_OPTIONAL };
+            h5try!(H5Pmodify_filter(plist_id, filter_id, flags, cd_nelmts, cd_values.as_ptr()));
+            Ok(())
+        })
+    }
+
     pub(crate) fn extract_pipeline(plist_id: hid_t) -> Result<Vec<Self>> {
+        Ok(Self::extract_pipeline_flags(plist_id)?.into_iter().map(|f| f.filter).collect())
+    }
+
+    pub(crate) fn extract_pipeline_flags(plist_id: hid_t) -> Result<Vec<PipelineFilter>> {
         let mut filters = Vec::new();
         let mut name: Vec<c_char> = vec![0; 257];
         let mut cd_values: Vec<c_uint> = vec![0; 32];
@@ -511,8 +603,8 @@ impl Filter {
                     ptr::null_mut(),
                 ));
                 let cdata = &cd_values[..(cd_nelmts as _)];
-                let flt = Self::from_raw(filter_id, cdata)?;
-                filters.push(flt);
+                let filter = Self::from_raw(filter_id, cdata)?;
+                filters.push(PipelineFilter { filter, optional: flags & H5Z_FLAG_OPTIONAL != 0 });
             }
             Ok(filters)
         })
@@ -527,6 +619,8 @@ pub(crate) fn validate_filters(filters: &[Filter], type_class: H5T_class_t) -> R
 
     for filter in filters {
         ensure!(filter.is_available(), "Filter not available: {:?}", filter);
+        // e.g. szip may be built with decoding support only, so existing data can be read
+        ensure!(filter.encode_enabled(), "Filter encoding not available: {:?}", filter);
 
         let id = filter.id();
 
@@ -573,12 +667,13 @@ pub(crate) fn validate_filters(filters: &[Filter], type_class: H5T_class_t) -> R
 mod tests {
     use hdf5_sys::h5t::H5T_class_t;
 
+    use super::H5Z_filter_t;
     use super::{
         blosc_available, deflate_available, lzf_available, szip_available, validate_filters,
         Filter, FilterInfo, SZip, ScaleOffset,
     };
     use crate::test::with_tmp_file;
-    use crate::{plist::DatasetCreate, Result};
+    use crate::{plist::DatasetCreate, File, Result};
 
     #[test]
     fn test_filter_pipeline() -> Result<()> {
@@ -655,4 +750,166 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_user_filter() {
+        use std::os::raw::c_uint;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use super::{
+            register_filter, register_filter_with_callbacks, unregister_filter, MAX_USER_FILTERS,
+        };
+        use crate::{Dataspace, Datatype};
+
+        const XOR_FILTER_ID: H5Z_filter_t = 300;
+        let (encoded, decoded) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (enc, dec) = (encoded.clone(), decoded.clone());
+        register_filter_with_callbacks(
+            XOR_FILTER_ID,
+            "xor",
+            Some(Box::new(|datatype: &Datatype, _: &Dataspace| -> Result<bool> {
+                ensure!(datatype.size() == 2, "expected 2-byte elements");
+                Ok(true)
+            })),
+            Some(Box::new(
+                |datatype: &Datatype, _: &Dataspace, cdata: &[c_uint]| -> Result<Vec<c_uint>> {
+                    Ok(cdata.iter().copied().chain(Some(datatype.size() as _)).collect())
+                },
+            )),
+            move |data, reverse, cdata| {
+                ensure!(cdata == [0x5a, 2], "expected a key and the element size");
+                let counter = if reverse { &dec } else { &enc };
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(data.iter().map(|&b| b ^ cdata[0] as u8).collect())
+            },
+        )
+        .unwrap();
+        // set_local appends the element size to the client data
+        let filter = Filter::user(XOR_FILTER_ID, &[0x5a, 2]);
+        assert!(filter.is_available());
+        assert!(filter.encode_enabled() && filter.decode_enabled());
+
+        with_tmp_file(|file| {
+            let data: Vec<u16> = (0..5000).map(|i| (i % 1000) as u16).collect();
+            let ds = file
+                .new_dataset_builder()
+                .add_filter(XOR_FILTER_ID, &[0x5a])
+                .chunk(1000)
+                .with_data(&data)
+                .create("x")
+                .unwrap();
+            assert_eq!(ds.filters(), vec![filter.clone()]);
+            // closing the dataset flushes the chunk cache
+            drop(ds);
+            assert_eq!(encoded.load(Ordering::SeqCst), 5);
+            let ds = file.dataset("x").unwrap();
+            assert_eq!(ds.read_raw::<u16>().unwrap(), data);
+            assert_eq!(decoded.load(Ordering::SeqCst), 5);
+            drop(ds);
+
+            // without the key the filter fails; since it's optional, chunks are left unfiltered
+            let ds = file
+                .new_dataset_builder()
+                .add_filter(XOR_FILTER_ID, &[])
+                .chunk(1000)
+                .with_data(&data)
+                .create("y")
+                .unwrap();
+            drop(ds);
+            assert_eq!(file.dataset("y").unwrap().read_raw::<u16>().unwrap(), data);
+
+            // can_apply rejects datasets with other element sizes
+            assert!(file
+                .new_dataset::<f64>()
+                .add_filter(XOR_FILTER_ID, &[0x5a])
+                .chunk(1000)
+                .shape(5000)
+                .create("z")
+                .is_err());
+        });
+
+        unregister_filter(XOR_FILTER_ID).unwrap();
+        assert!(!filter.is_available());
+        assert!(unregister_filter(XOR_FILTER_ID).is_err());
+
+        // unregistering a filter flushes all open files, which runs other custom filters
+        const NOOP_FILTER_ID: H5Z_filter_t = 310;
+        const NOT_FILTER_ID: H5Z_filter_t = 311;
+        register_filter(NOOP_FILTER_ID, "noop", |data, _, _| Ok(data.to_vec())).unwrap();
+        let not_encoded = Arc::new(AtomicUsize::new(0));
+        let counter = not_encoded.clone();
+        register_filter(NOT_FILTER_ID, "not", move |data, reverse, _| {
+            if !reverse {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(data.iter().map(|&b| !b).collect())
+        })
+        .unwrap();
+        with_tmp_file(|file| {
+            let data: Vec<u8> = (0..=255).collect();
+            let ds = file
+                .new_dataset_builder()
+                .add_filter(NOT_FILTER_ID, &[])
+                .chunk(64)
+                .with_data(&data)
+                .create("x")
+                .unwrap();
+            assert_eq!(not_encoded.load(Ordering::SeqCst), 0);
+            unregister_filter(NOOP_FILTER_ID).unwrap();
+            assert_eq!(not_encoded.load(Ordering::SeqCst), 4);
+            drop(ds);
+            assert_eq!(file.dataset("x").unwrap().read_raw::<u8>().unwrap(), data);
+        });
+        unregister_filter(NOT_FILTER_ID).unwrap();
+
+        let ids: Vec<H5Z_filter_t> =
+            (0..MAX_USER_FILTERS as H5Z_filter_t).map(|i| 301 + i).collect();
+        for &id in &ids {
+            register_filter(id, "noop", |data, _, _| Ok(data.to_vec())).unwrap();
+        }
+        assert_err!(
+            register_filter(XOR_FILTER_ID, "xor", |data, _, _| Ok(data.to_vec())),
+            "all 8 custom filter slots are in use"
+        );
+        for &id in &ids {
+            unregister_filter(id).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_szip() {
+        let filter = Filter::szip(SZip::NearestNeighbor, 16);
+        if !szip_available() {
+            return;
+        }
+        assert!(filter.decode_enabled());
+
+        // the fixture is generated without the library (see `make_szip.py`), so existing szip
+        // data must be readable whenever decoding is available, even if encoding is not
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/szip.h5");
+        let ds = File::open(path).unwrap().dataset("data").unwrap();
+        assert_eq!(ds.filters(), vec![Filter::szip(SZip::Entropy, 16)]);
+        let expected: Vec<u16> = (0..64).map(|i| i * 1000).collect();
+        assert_eq!(ds.read_raw::<u16>().unwrap(), expected);
+
+        with_tmp_file(|file| {
+            let builder = || {
+                file.new_dataset::<u16>()
+                    .shape((100, 50))
+                    .chunk((25, 50))
+                    .szip(SZip::NearestNeighbor, 16)
+            };
+            if !filter.encode_enabled() {
+                assert_err!(builder().create("x"), "Filter encoding not available");
+                return;
+            }
+            let ds = builder().create("x").unwrap();
+            assert_eq!(ds.filters(), vec![filter.clone()]);
+            let data: Vec<u16> = (0..5000).map(|i| (i % 100) as u16).collect();
+            ds.write_raw(&data).unwrap();
+            assert_eq!(ds.read_raw::<u16>().unwrap(), data);
+            assert!(ds.storage_size() < 5000 * 2);
+        })
+    }
 }
@@ -0,0 +1,316 @@
+use std::ffi::CString;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use hdf5_sys::h5::htri_t;
+use hdf5_sys::h5p::{H5Pget_filter_by_id2, H5Pmodify_filter};
+use hdf5_sys::h5s::H5Scopy;
+use hdf5_sys::h5t::H5Tcopy;
+use hdf5_sys::h5z::{
+    H5Z_can_apply_func_t, H5Z_class2_t, H5Z_filter_t, H5Z_func_t, H5Z_set_local_func_t,
+    H5Zregister, H5Zunregister, H5Z_CLASS_T_VERS, H5Z_FLAG_REVERSE,
+};
+
+use crate::globals::{H5E_CALLBACK, H5E_PLIST};
+use crate::internal_prelude::*;
+
+/// Maximum number of custom filters that can be registered at the same time.
+///
+/// The library calls filters through plain function pointers without any user data, so
+/// each registered filter occupies one of a fixed number of pre-generated entry points.
+pub const MAX_USER_FILTERS: usize = 8;
+
+type FilterFn = dyn Fn(&[u8], bool, &[c_uint]) -> Result<Vec<u8>> + Send + Sync;
+
+/// Callback deciding whether a custom filter can be applied to a dataset with the given
+/// datatype and dataspace (see [`register_filter_with_callbacks()`]).
+pub type CanApplyFn = dyn Fn(&Datatype, &Dataspace) -> Result<bool> + Send + Sync;
+
+/// Callback returning the client data values of a custom filter for a dataset with the
+/// given datatype and dataspace, given the values it was added with (see
+/// [`register_filter_with_callbacks()`]).
+pub type SetLocalFn = dyn Fn(&Datatype, &Dataspace, &[c_uint]) -> Result<Vec<c_uint>> + Send + Sync;
+
+struct UserFilter {
+    id: H5Z_filter_t,
+    func: Arc<FilterFn>,
+    can_apply: Option<Arc<CanApplyFn>>,
+    set_local: Option<Arc<SetLocalFn>>,
+    // the library keeps the name pointer, so it must outlive the registration
+    _name: CString,
+}
+
+lazy_static! {
+    static ref USER_FILTERS: Mutex<Vec<Option<UserFilter>>> =
+        Mutex::new((0..MAX_USER_FILTERS).map(|_| None).collect());
+}
+
+const FILTER_FUNCS: [H5Z_func_t; MAX_USER_FILTERS] = [
+    Some(filter_user::<0>),
+    Some(filter_user::<1>),
+    Some(filter_user::<2>),
+    Some(filter_user::<3>),
+    Some(filter_user::<4>),
+    Some(filter_user::<5>),
+    Some(filter_user::<6>),
+    Some(filter_user::<7>),
+];
+
+const CAN_APPLY_FUNCS: [H5Z_can_apply_func_t; MAX_USER_FILTERS] = [
+    Some(can_apply_user::<0>),
+    Some(can_apply_user::<1>),
+    Some(can_apply_user::<2>),
+    Some(can_apply_user::<3>),
+    Some(can_apply_user::<4>),
+    Some(can_apply_user::<5>),
+    Some(can_apply_user::<6>),
+    Some(can_apply_user::<7>),
+];
+
+const SET_LOCAL_FUNCS: [H5Z_set_local_func_t; MAX_USER_FILTERS] = [
+    Some(set_local_user::<0>),
+    Some(set_local_user::<1>),
+    Some(set_local_user::<2>),
+    Some(set_local_user::<3>),
+    Some(set_local_user::<4>),
+    Some(set_local_user::<5>),
+    Some(set_local_user::<6>),
+    Some(set_local_user::<7>),
+];
+
+/// Registers a custom filter implemented in Rust with the HDF5 library.
+///
+/// `filter` is called for each chunk with the chunk data, a flag which is `false` when
+/// encoding (writing) and `true` when decoding (reading), and the client data values of the
+/// filter (as passed to `DatasetBuilder::add_filter()`), and should return the encoded or
+/// decoded data; returning an error (or empty data) makes the filter fail. Note that the
+/// filter is invoked while the library is busy, so it must not call into HDF5 itself.
+///
+/// Once registered, the filter can be added to datasets via `DatasetBuilder::add_filter()`
+/// or `Filter::user()`, using the same `id`; custom filters are added as optional filters.
+/// Filter ids from 256 to 511 are reserved for testing, ids from 32768 to 65535 are
+/// available for private use; other ids are assigned by The HDF Group.
+///
+/// At most [`MAX_USER_FILTERS`] filters can be registered at once, and registering more
+/// fails until one of them is unregistered; registering a filter with an id that has
+/// already been registered via this function replaces it.
+///
+/// See [`register_filter_with_callbacks()`] for filters that need to inspect or adapt to
+/// the datasets they're applied to.
+pub fn register_filter<F>(id: H5Z_filter_t, name: &str, filter: F) -> Result<()>
+where
+    F: Fn(&[u8], bool, &[c_uint]) -> Result<Vec<u8>> + Send + Sync + 'static,
+{
+    register_filter_with_callbacks(id, name, None, None, filter)
+}
+
+/// Registers a custom filter implemented in Rust, with optional dataset creation callbacks.
+///
+/// This is like [`register_filter()`], except that two optional callbacks can be provided,
+/// which are invoked each time a dataset using the filter is created:
+///
+/// - `can_apply` is called with the datatype and dataspace of the dataset and should return
+///   whether the filter can be applied to it; returning an error fails dataset creation.
+/// - `set_local` is called afterwards with the datatype, the dataspace and the client data
+///   values the filter was added with, and should return the client data values to store
+///   for this dataset (e.g. with the element size appended), which are then passed to
+///   `filter` on each call.
+///
+/// Both callbacks receive copies of the dataset's datatype and dataspace; like `filter`,
+/// they're invoked while the library is busy and must not call into HDF5 otherwise.
+pub fn register_filter_with_callbacks<F>(
+    id: H5Z_filter_t, name: &str, can_apply: Option<Box<CanApplyFn>>,
+    set_local: Option<Box<SetLocalFn>>, filter: F,
+) -> Result<()>
+where
+    F: Fn(&[u8], bool, &[c_uint]) -> Result<Vec<u8>> + Send + Sync + 'static,
+{
+    ensure!(id > 0, "invalid filter id: {}", id);
+    let name = to_cstring(name)?;
+    h5lock!({
+        let mut filters = USER_FILTERS.lock();
+        let slot = filters
+            .iter()
+            .position(|f| f.as_ref().map_or(false, |f| f.id == id))
+            .or_else(|| filters.iter().position(Option::is_none))
+            .ok_or_else(|| {
+                format!(
+                    "Can't register custom filter {}: all {} custom filter slots are in use \
+                     (unregister a filter first)",
+                    id, MAX_USER_FILTERS
+                )
+            })?;
+        let class = H5Z_class2_t {
+            version: H5Z_CLASS_T_VERS as _,
+            id,
+            encoder_present: 1,
+            decoder_present: 1,
+            name: name.as_ptr(),
+            can_apply: can_apply.as_ref().and(CAN_APPLY_FUNCS[slot]),
+            set_local: set_local.as_ref().and(SET_LOCAL_FUNCS[slot]),
+            filter: FILTER_FUNCS[slot],
+        };
+        h5try!(H5Zregister((&class as *const H5Z_class2_t).cast()));
+        filters[slot] = Some(UserFilter {
+            id,
+            func: Arc::new(filter),
+            can_apply: can_apply.map(Arc::from),
+            set_local: set_local.map(Arc::from),
+            _name: name,
+        });
+        Ok(())
+    })
+}
+
+/// Unregisters a custom filter registered via [`register_filter()`].
+///
+/// This fails if the filter is still in use by any open datasets.
+pub fn unregister_filter(id: H5Z_filter_t) -> Result<()> {
+    h5lock!({
+        let slot = USER_FILTERS
+            .lock()
+            .iter()
+            .position(|f| f.as_ref().map_or(false, |f| f.id == id))
+            .ok_or_else(|| format!("Custom filter {} is not registered", id))?;
+        // the library flushes all open files here, which may invoke other custom filters
+        // (and lock the filter table), so it must not be held while unregistering
+        h5try!(H5Zunregister(id));
+        USER_FILTERS.lock()[slot] = None;
+        Ok(())
+    })
+}
+
+extern "C" fn filter_user<const N: usize>(
+    flags: c_uint, cd_nelmts: size_t, cd_values: *const c_uint, nbytes: size_t,
+    buf_size: *mut size_t, buf: *mut *mut c_void,
+) -> size_t {
+    let func = match USER_FILTERS.lock()[N] {
+        Some(ref filter) => Arc::clone(&filter.func),
+        None => {
+            h5err!("Custom filter is not registered", H5E_PLIST, H5E_CALLBACK);
+            return 0;
+        }
+    };
+    let cdata = if cd_nelmts == 0 || cd_values.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(cd_values, cd_nelmts as _) }
+    };
+    let data = unsafe { slice::from_raw_parts((*buf).cast::<u8>(), nbytes as _) };
+    let reverse = flags & H5Z_FLAG_REVERSE != 0;
+    let out = match panic::catch_unwind(AssertUnwindSafe(|| func(data, reverse, cdata))) {
+        Ok(Ok(out)) if !out.is_empty() => out,
+        Ok(Ok(_)) => {
+            h5err!("Custom filter returned no data", H5E_PLIST, H5E_CALLBACK);
+            return 0;
+        }
+        Ok(Err(err)) => {
+            h5err!(err.to_string().as_str(), H5E_PLIST, H5E_CALLBACK);
+            return 0;
+        }
+        Err(_) => {
+            h5err!("Custom filter panicked", H5E_PLIST, H5E_CALLBACK);
+            return 0;
+        }
+    };
+    unsafe {
+        let outbuf = libc::malloc(out.len());
+        if outbuf.is_null() {
+            h5err!("Can't allocate filter output buffer", H5E_PLIST, H5E_CALLBACK);
+            return 0;
+        }
+        ptr::copy_nonoverlapping(out.as_ptr(), outbuf.cast::<u8>(), out.len());
+        libc::free(*buf);
+        *buf = outbuf;
+        *buf_size = out.len() as _;
+    }
+    out.len() as _
+}
+
+/// Wraps copies of the (library-internal) datatype and dataspace ids passed to callbacks.
+fn callback_args(type_id: hid_t, space_id: hid_t) -> Result<(Datatype, Dataspace)> {
+    let datatype = Datatype::from_id(h5try!(H5Tcopy(type_id)))?;
+    let dataspace = Dataspace::from_id(h5try!(H5Scopy(space_id)))?;
+    Ok((datatype, dataspace))
+}
+
+extern "C" fn can_apply_user<const N: usize>(
+    _dcpl_id: hid_t, type_id: hid_t, space_id: hid_t,
+) -> htri_t {
+    let func = match USER_FILTERS.lock()[N] {
+        Some(UserFilter { can_apply: Some(ref func), .. }) => Arc::clone(func),
+        _ => {
+            h5err!("Custom filter callback is not registered", H5E_PLIST, H5E_CALLBACK);
+            return -1;
+        }
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (datatype, dataspace) = callback_args(type_id, space_id)?;
+        func(&datatype, &dataspace)
+    }));
+    match result {
+        Ok(Ok(can_apply)) => can_apply as _,
+        Ok(Err(err)) => {
+            h5err!(err.to_string().as_str(), H5E_PLIST, H5E_CALLBACK);
+            -1
+        }
+        Err(_) => {
+            h5err!("Custom filter callback panicked", H5E_PLIST, H5E_CALLBACK);
+            -1
+        }
+    }
+}
+
+extern "C" fn set_local_user<const N: usize>(
+    dcpl_id: hid_t, type_id: hid_t, space_id: hid_t,
+) -> herr_t {
+    let (id, func) = match USER_FILTERS.lock()[N] {
+        Some(UserFilter { id, set_local: Some(ref func), .. }) => (id, Arc::clone(func)),
+        _ => {
+            h5err!("Custom filter callback is not registered", H5E_PLIST, H5E_CALLBACK);
+            return -1;
+        }
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> Result<()> {
+        let (datatype, dataspace) = callback_args(type_id, space_id)?;
+        let mut flags: c_uint = 0;
+        let mut cd_values: Vec<c_uint> = vec![0; 32];
+        loop {
+            let mut cd_nelmts: size_t = cd_values.len() as _;
+            h5try!(H5Pget_filter_by_id2(
+                dcpl_id,
+                id,
+                &mut flags as *mut _,
+                &mut cd_nelmts as *mut _,
+                cd_values.as_mut_ptr(),
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ));
+            if (cd_nelmts as usize) <= cd_values.len() {
+                cd_values.truncate(cd_nelmts as _);
+                break;
+            }
+            cd_values.resize(cd_nelmts as _, 0);
+        }
+        let cd_values = func(&datatype, &dataspace, &cd_values)?;
+        h5try!(H5Pmodify_filter(dcpl_id, id, flags, cd_values.len() as _, cd_values.as_ptr()));
+        Ok(())
+    }));
+    match result {
+        Ok(Ok(())) => 0,
+        Ok(Err(err)) => {
+            h5err!(err.to_string().as_str(), H5E_PLIST, H5E_CALLBACK);
+            -1
+        }
+        Err(_) => {
+            h5err!("Custom filter callback panicked", H5E_PLIST, H5E_CALLBACK);
+            -1
+        }
+    }
+}
@@ -1,20 +1,30 @@
+use std::collections::{HashSet, VecDeque};
 use std::fmt::{self, Debug};
+use std::iter;
+use std::mem;
 use std::ops::Deref;
 use std::panic;
+use std::ptr;
+
+use ndarray::{ArrayView, IxDyn};
 
 use hdf5_sys::{
     h5::{hsize_t, H5_index_t, H5_iter_order_t},
-    h5d::H5Dopen2,
-    h5g::{H5G_info_t, H5Gcreate2, H5Gget_info, H5Gopen2},
+    h5d::{H5Dcreate2, H5Dopen2},
+    h5g::{H5G_info_t, H5Gcreate2, H5Gget_create_plist, H5Gget_info, H5Gopen2},
     h5l::{
         H5L_info_t, H5L_iterate_t, H5L_type_t, H5Lcreate_external, H5Lcreate_hard, H5Lcreate_soft,
-        H5Ldelete, H5Lexists, H5Literate, H5Lmove, H5L_SAME_LOC,
+        H5Ldelete, H5Lexists, H5Lget_val, H5Literate, H5Lmove, H5Lunpack_elink_val, H5L_SAME_LOC,
     },
+    h5o::{H5Olink, H5Oopen},
     h5p::{H5Pcreate, H5Pset_create_intermediate_group},
     h5t::H5T_cset_t,
 };
 
 use crate::globals::H5P_LINK_CREATE;
+use crate::hl::plist::dataset_access::DatasetAccess;
+use crate::hl::plist::group_create::{GroupCreate, GroupCreateBuilder, LinkCreationOrder};
+use crate::hl::plist::link_access::LinkAccess;
 use crate::internal_prelude::*;
 use crate::{Location, LocationType};
 
@@ -82,20 +92,31 @@ impl Group {
         self.len() == 0
     }
 
+    /// Returns a copy of the group creation property list.
+    pub fn create_plist(&self) -> Result<GroupCreate> {
+        h5lock!(GroupCreate::from_id(h5try!(H5Gget_create_plist(self.id()))))
+    }
+
+    /// A short alias for `create_plist()`.
+    pub fn gcpl(&self) -> Result<GroupCreate> {
+        self.create_plist()
+    }
+
     /// Create a new group in a file or group.
     pub fn create_group(&self, name: &str) -> Result<Self> {
         // TODO: &mut self?
-        h5lock!({
-            let lcpl = make_lcpl()?;
-            let name = to_cstring(name)?;
-            Self::from_id(h5try!(H5Gcreate2(
-                self.id(),
-                name.as_ptr(),
-                lcpl.id(),
-                H5P_DEFAULT,
-                H5P_DEFAULT
-            )))
-        })
+        self.new_group_builder().create(name)
+    }
+
+    /// Create a new group in a file or group, using a copy of the given group creation
+    /// property list (see also [`GroupBuilder::set_gcpl()`]).
+    pub fn create_group_with_plist(&self, name: &str, gcpl: &GroupCreate) -> Result<Self> {
+        self.new_group_builder().set_gcpl(gcpl).create(name)
+    }
+
+    /// Instantiates a new group builder, which allows setting group creation properties.
+    pub fn new_group_builder(&self) -> GroupBuilder {
+        GroupBuilder::new(self)
     }
 
     /// Opens an existing group in a file or group.
@@ -104,6 +125,15 @@ impl Group {
         Self::from_id(h5try!(H5Gopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
     }
 
+    /// Opens an existing group in a file or group, or creates it if it doesn't exist.
+    pub fn require_group(&self, name: &str) -> Result<Self> {
+        if self.link_exists(name) {
+            self.group(name)
+        } else {
+            self.create_group(name)
+        }
+    }
+
     /// Creates a soft link.
     ///
     /// A soft link does not require the linked object to exist.
@@ -141,6 +171,22 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Creates a hard link to an existing object, which may be located elsewhere in the
+    /// same file or be anonymous, i.e. not linked into the hierarchy yet.
+    ///
+    /// Anonymous datasets can be created by passing `None` as the name to
+    /// `DatasetBuilder::create()`; this allows to write the data first and then to make
+    /// the dataset visible to other readers (e.g. in SWMR mode) at once by linking it.
+    /// Note: `link_name` is relative to the current object; intermediate groups are created.
+    pub fn link_object(&self, obj: &Location, link_name: &str) -> Result<()> {
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let link_name = to_cstring(link_name)?;
+            h5call!(H5Olink(obj.id(), self.id(), link_name.as_ptr(), lcpl.id(), H5P_DEFAULT))
+                .and(Ok(()))
+        })
+    }
+
     /// Creates an external link.
     ///
     /// Note: `link_name` is relative to the current object,
@@ -183,6 +229,25 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Moves (and/or renames) a link to an object from this group to another group, which
+    /// must be in the same file. Note: `name` is relative to this group, and `dst_name` is
+    /// relative to `dst_group`.
+    ///
+    /// To rename a link within the same group, use [`relink`](Self::relink).
+    pub fn move_link(&self, name: &str, dst_group: &Self, dst_name: &str) -> Result<()> {
+        let name = to_cstring(name)?;
+        let dst_name = to_cstring(dst_name)?;
+        h5call!(H5Lmove(
+            self.id(),
+            name.as_ptr(),
+            dst_group.id(),
+            dst_name.as_ptr(),
+            H5P_DEFAULT,
+            H5P_DEFAULT
+        ))
+        .and(Ok(()))
+    }
+
     /// Removes a link to an object from this file or group.
     pub fn unlink(&self, name: &str) -> Result<()> {
         // TODO: &mut self?
@@ -190,6 +255,53 @@ impl Group {
         h5call!(H5Ldelete(self.id(), name.as_ptr(), H5P_DEFAULT)).and(Ok(()))
     }
 
+    /// Returns information about the link with the given name (without resolving the link).
+    pub fn link_info(&self, name: &str) -> Result<LinkInfo> {
+        h5lock!(get_link_info(self.id(), name).map(|info| LinkInfo::from(&info)))
+    }
+
+    /// Returns the target of the link with the given name (without resolving the link).
+    ///
+    /// This also works for soft and external links whose targets don't exist.
+    pub fn link_target(&self, name: &str) -> Result<LinkTarget> {
+        h5lock!({
+            let mut info = get_link_info(self.id(), name)?;
+            if info.type_ == H5L_type_t::H5L_TYPE_HARD {
+                return Ok(LinkTarget::Hard);
+            }
+            let is_soft = info.type_ == H5L_type_t::H5L_TYPE_SOFT;
+            ensure!(
+                is_soft || info.type_ == H5L_type_t::H5L_TYPE_EXTERNAL,
+                "Unsupported link type: {:?}",
+                info.type_
+            );
+            let size = *info.u.val_size();
+            let mut buf = vec![0_u8; size];
+            let name = to_cstring(name)?;
+            h5try!(H5Lget_val(
+                self.id(),
+                name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                size,
+                H5P_DEFAULT
+            ));
+            if is_soft {
+                Ok(LinkTarget::Soft(string_from_cstr(buf.as_ptr().cast())))
+            } else {
+                let (mut flags, mut filename, mut path) = (0, ptr::null(), ptr::null());
+                h5try!(H5Lunpack_elink_val(
+                    buf.as_ptr().cast(),
+                    size,
+                    &mut flags,
+                    &mut filename,
+                    &mut path
+                ));
+                let (filename, path) = (string_from_cstr(filename), string_from_cstr(path));
+                Ok(LinkTarget::External { filename, path })
+            }
+        })
+    }
+
     /// Check if a link with a given name exists in this file or group.
     pub fn link_exists(&self, name: &str) -> bool {
         (|| -> Result<bool> {
@@ -209,11 +321,144 @@ impl Group {
         DatasetBuilder::new(self)
     }
 
+    /// Creates a new dataset with the shape and the type of the given array and writes
+    /// the array into it.
+    ///
+    /// This is a shortcut for `new_dataset_builder().with_data(data).create(name)`; use
+    /// the builder to customize the dataset (e.g. chunking, filters or resizability).
+    pub fn new_dataset_from<'d, A, T, D>(&self, name: &str, data: A) -> Result<Dataset>
+    where
+        A: Into<ArrayView<'d, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        self.new_dataset_builder().with_data(data).create(name)
+    }
+
+    /// Creates a new dataset with the given shape and fills it with the elements produced
+    /// by the iterator, in row-major order.
+    ///
+    /// The elements are written in batches of whole rows (along the first axis), so the
+    /// memory used for buffering is bounded regardless of the size of the dataset. The
+    /// iterator must produce exactly as many elements as there are in the dataset.
+    pub fn new_dataset_from_iter<T, S, I>(&self, name: &str, shape: S, iter: I) -> Result<Dataset>
+    where
+        T: H5Type,
+        S: Into<Extents>,
+        I: IntoIterator<Item = T>,
+    {
+        const BATCH_BYTES: usize = 1 << 20;
+
+        let ds = self.new_dataset::<T>().shape(shape).create(name)?;
+        let (shape, size) = (ds.shape(), ds.size());
+        let mut iter = iter.into_iter();
+        let mut written = 0;
+        if size > 0 && shape.is_empty() {
+            if let Some(value) = iter.next() {
+                ds.write_scalar(&value)?;
+                written = 1;
+            }
+        } else if size > 0 {
+            let row_size: usize = shape[1..].iter().product();
+            let batch_rows = (BATCH_BYTES / (row_size * mem::size_of::<T>()).max(1)).max(1);
+            let mut buf = Vec::with_capacity(batch_rows.min(shape[0]) * row_size);
+            while written < size {
+                let row = written / row_size;
+                let n_rows = batch_rows.min(shape[0] - row);
+                buf.clear();
+                buf.extend(iter.by_ref().take(n_rows * row_size));
+                if buf.len() < n_rows * row_size {
+                    written += buf.len();
+                    break;
+                }
+                let mut batch_shape = shape.clone();
+                batch_shape[0] = n_rows;
+                let batch = ArrayView::from_shape(IxDyn(&batch_shape), &buf)?;
+                let rest = shape[1..].iter().map(|_| SliceOrIndex::from(..));
+                let selection: Vec<_> =
+                    iter::once((row..row + n_rows).into()).chain(rest).collect();
+                ds.write_slice(batch, Hyperslab::from(selection))?;
+                written += buf.len();
+            }
+        }
+        ensure!(written == size, "Iterator produced {} elements, expected {}", written, size);
+        ensure!(iter.next().is_none(), "Iterator produced more than {} elements", size);
+        Ok(ds)
+    }
+
     /// Opens an existing dataset in the file or group.
     pub fn dataset(&self, name: &str) -> Result<Dataset> {
         let name = to_cstring(name)?;
         Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
     }
+
+    /// Opens an existing dataset in the file or group with the given dataset access
+    /// properties (e.g. a custom chunk cache or a prefix for external storage files).
+    pub fn dataset_with_dapl(&self, name: &str, dapl: &DatasetAccess) -> Result<Dataset> {
+        let name = to_cstring(name)?;
+        Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), dapl.id())))
+    }
+
+    /// Opens an existing object in the file or group, resolving the path with the given
+    /// link access properties (e.g. a higher limit of soft link traversals).
+    pub fn open_with_lapl(&self, name: &str, lapl: &LinkAccess) -> Result<Location> {
+        let name = to_cstring(name)?;
+        Location::from_id(h5try!(H5Oopen(self.id(), name.as_ptr(), lapl.id())))
+    }
+
+    /// Opens an existing dataset in the file or group, or creates it if it doesn't exist.
+    ///
+    /// If the dataset already exists, its shape must be equal to `shape` and its datatype
+    /// must be convertible to `T`, otherwise an error is returned. New datasets are created
+    /// with the default settings; use `new_dataset_builder()` for finer control.
+    pub fn require_dataset<T: H5Type>(
+        &self, name: &str, shape: impl Into<Extents>,
+    ) -> Result<Dataset> {
+        let extents = shape.into();
+        if !self.link_exists(name) {
+            return self.new_dataset::<T>().shape(extents).create(name);
+        }
+        let ds = self.dataset(name)?;
+        let (expected, actual) = (extents.dims(), ds.shape());
+        ensure!(
+            expected == actual,
+            "shape mismatch for dataset \"{}\": expected {:?}, got {:?}",
+            name,
+            expected,
+            actual
+        );
+        ensure!(
+            ds.dtype()?.conv_to::<T>().is_some(),
+            "datatype of dataset \"{}\" is not convertible to {:?}",
+            name,
+            T::type_descriptor()
+        );
+        Ok(ds)
+    }
+
+    /// Creates a new empty dataset with the same datatype, shape (including maximum
+    /// dimensions) and creation properties (layout, chunking, filters, fill value, etc.)
+    /// as the `template` dataset; no data is copied over.
+    ///
+    /// The template may belong to a different file.
+    pub fn create_dataset_like(&self, name: &str, template: &Dataset) -> Result<Dataset> {
+        h5lock!({
+            let dtype = template.dtype()?;
+            let space = Dataspace::try_new(template.space()?.extents()?)?;
+            let dcpl = template.dcpl()?;
+            let lcpl = make_lcpl()?;
+            let name = to_cstring(name)?;
+            Dataset::from_id(h5try!(H5Dcreate2(
+                self.id(),
+                name.as_ptr(),
+                dtype.id(),
+                space.id(),
+                lcpl.id(),
+                dcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -293,6 +538,27 @@ impl From<&H5L_info_t> for LinkInfo {
     }
 }
 
+/// The target of a link, as stored in the link itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// A hard link, which points directly to an object.
+    Hard,
+    /// A soft link with the path to the target object.
+    Soft(String),
+    /// An external link with the name of the target file and the path to the object in it.
+    External { filename: String, path: String },
+}
+
+/// Whether to continue or stop visiting links or attributes, as returned by the closures
+/// passed to [`Group::for_each_link()`] and [`Location::for_each_attr()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterAction {
+    /// Proceed to the next link or attribute.
+    Continue,
+    /// Stop the iteration early (this is not an error).
+    Stop,
+}
+
 /// Iteration methods
 impl Group {
     /// Visits all objects in the group
@@ -301,7 +567,7 @@ impl Group {
         mut op: F,
     ) -> Result<G>
     where
-        F: Fn(&Self, &str, LinkInfo, &mut G) -> bool,
+        F: FnMut(&Self, &str, LinkInfo, &mut G) -> bool,
     {
         /// Struct used to pass a tuple
         struct Vtable<'a, F, D> {
@@ -355,13 +621,33 @@ impl Group {
     /// Visits all objects in the group using default iteration/traversal order.
     pub fn iter_visit_default<F, G>(&self, val: G, op: F) -> Result<G>
     where
-        F: Fn(&Self, &str, LinkInfo, &mut G) -> bool,
+        F: FnMut(&Self, &str, LinkInfo, &mut G) -> bool,
     {
         self.iter_visit(IterationOrder::default(), TraversalOrder::default(), val, op)
     }
 
+    /// Visits the names of all links in the group using default iteration/traversal order,
+    /// passing the index of each link along with its name.
+    ///
+    /// Returning [`IterAction::Stop`] from the closure stops the iteration early, so there's
+    /// no need to collect all the names in a large group to find the ones of interest.
+    pub fn for_each_link<F>(&self, mut op: F) -> Result<()>
+    where
+        F: FnMut(usize, &str) -> IterAction,
+    {
+        self.iter_visit_default(0, |_, name, _, index| {
+            let action = op(*index, name);
+            *index += 1;
+            action == IterAction::Continue
+        })
+        .and(Ok(()))
+    }
+
     fn get_all_of_type(&self, loc_type: LocationType) -> Result<Vec<Location>> {
-        self.iter_visit_default(vec![], |group, name, _info, objects| {
+        self.iter_visit_default(vec![], |group, name, link_info, objects| {
+            if link_info.link_type != LinkType::Hard {
+                return true; // soft and external links are not followed
+            }
             if let Ok(info) = group.loc_info_by_name(name) {
                 if info.loc_type == loc_type {
                     if let Ok(loc) = group.open_by_token(info.token) {
@@ -376,19 +662,25 @@ impl Group {
         })
     }
 
-    /// Returns all groups in the group, non-recursively
+    /// Returns all groups in the group, non-recursively.
+    ///
+    /// Only hard links are considered; soft and external links are skipped.
     pub fn groups(&self) -> Result<Vec<Self>> {
         self.get_all_of_type(LocationType::Group)
             .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
     }
 
-    /// Returns all datasets in the group, non-recursively
+    /// Returns all datasets in the group, non-recursively.
+    ///
+    /// Only hard links are considered; soft and external links are skipped.
     pub fn datasets(&self) -> Result<Vec<Dataset>> {
         self.get_all_of_type(LocationType::Dataset)
             .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
     }
 
-    /// Returns all named types in the group, non-recursively
+    /// Returns all named types in the group, non-recursively.
+    ///
+    /// Only hard links are considered; soft and external links are skipped.
     pub fn named_datatypes(&self) -> Result<Vec<Datatype>> {
         self.get_all_of_type(LocationType::NamedDatatype)
             .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
@@ -401,6 +693,159 @@ impl Group {
             true
         })
     }
+
+    /// Returns the full paths of all datasets in the group and its subgroups, recursively.
+    ///
+    /// The hierarchy is traversed breadth-first and each dataset is reported exactly once,
+    /// under the first path it is found at, even if it is reachable via multiple links
+    /// (hard links are visited before soft links within each group). If `follow_links` is
+    /// true, soft and external links are resolved and followed, with dangling links being
+    /// skipped; otherwise, only hard links are considered.
+    ///
+    /// Calling this on a [`File`] (which dereferences to its root group) lists all datasets
+    /// in the file.
+    pub fn dataset_paths(&self, follow_links: bool) -> Result<Vec<String>> {
+        let info = self.loc_info()?;
+        let mut visited = HashSet::new();
+        visited.insert((info.fileno, info.token));
+        let mut queue = VecDeque::new();
+        queue.push_back((self.clone(), self.name()));
+        let mut paths = vec![];
+        while let Some((group, path)) = queue.pop_front() {
+            let mut links = group.iter_visit_default(vec![], |_, name, info, links| {
+                links.push((name.to_owned(), info.link_type));
+                true
+            })?;
+            links.sort_by_key(|&(_, link_type)| link_type != LinkType::Hard);
+            for (name, link_type) in links {
+                if link_type != LinkType::Hard && !follow_links {
+                    continue;
+                }
+                let info = match group.loc_info_by_name(&name) {
+                    Ok(info) => info,
+                    Err(_) if link_type != LinkType::Hard => continue, // dangling link
+                    Err(err) => return Err(err),
+                };
+                if !visited.insert((info.fileno, info.token)) {
+                    continue;
+                }
+                let full_path = if path.ends_with('/') {
+                    format!("{}{}", path, name)
+                } else {
+                    format!("{}/{}", path, name)
+                };
+                match info.loc_type {
+                    LocationType::Dataset => paths.push(full_path),
+                    LocationType::Group => queue.push_back((group.group(&name)?, full_path)),
+                    _ => {}
+                }
+            }
+        }
+        Ok(paths)
+    }
+}
+
+/// A builder used to create groups with custom group creation properties.
+#[derive(Clone)]
+pub struct GroupBuilder {
+    parent: Result<Handle>,
+    gcpl_base: Option<GroupCreate>,
+    gcpl_builder: GroupCreateBuilder,
+}
+
+impl GroupBuilder {
+    pub fn new(parent: &Group) -> Self {
+        Self {
+            parent: parent.try_borrow(),
+            gcpl_base: None,
+            gcpl_builder: GroupCreateBuilder::default(),
+        }
+    }
+
+    /// Uses a copy of the given group creation property list as a base.
+    #[must_use]
+    pub fn set_gcpl(mut self, gcpl: &GroupCreate) -> Self {
+        self.gcpl_base = Some(gcpl.clone());
+        self
+    }
+
+    /// Returns the group creation property list builder.
+    pub fn gcpl(&mut self) -> &mut GroupCreateBuilder {
+        &mut self.gcpl_builder
+    }
+
+    #[must_use]
+    pub fn with_gcpl<F>(mut self, func: F) -> Self
+    where
+        F: Fn(&mut GroupCreateBuilder) -> &mut GroupCreateBuilder,
+    {
+        func(&mut self.gcpl_builder);
+        self
+    }
+
+    /// Sets the estimated number of links in the group and the average length of their names
+    /// (see [`GroupCreateBuilder::est_link_info()`]).
+    #[must_use]
+    pub fn est_link_info(self, num_entries: u32, name_len: u32) -> Self {
+        self.with_gcpl(|pl| pl.est_link_info(num_entries, name_len))
+    }
+
+    /// Sets link storage phase change thresholds
+    /// (see [`GroupCreateBuilder::link_phase_change()`]).
+    #[must_use]
+    pub fn link_phase_change(self, max_compact: u32, min_dense: u32) -> Self {
+        self.with_gcpl(|pl| pl.link_phase_change(max_compact, min_dense))
+    }
+
+    /// Sets flags for tracking and indexing link creation order
+    /// (see [`GroupCreateBuilder::link_creation_order()`]).
+    #[must_use]
+    pub fn link_creation_order(self, link_creation_order: LinkCreationOrder) -> Self {
+        self.with_gcpl(|pl| pl.link_creation_order(link_creation_order))
+    }
+
+    /// Sets whether the times associated with the group are recorded
+    /// (see [`GroupCreateBuilder::obj_track_times()`]).
+    #[must_use]
+    pub fn obj_track_times(self, track_times: bool) -> Self {
+        self.with_gcpl(|pl| pl.obj_track_times(track_times))
+    }
+
+    fn build_gcpl(&self) -> Result<GroupCreate> {
+        let mut gcpl = match &self.gcpl_base {
+            Some(gcpl) => gcpl.clone(),
+            None => GroupCreate::try_new()?,
+        };
+        self.gcpl_builder.apply(&mut gcpl).map(|_| gcpl)
+    }
+
+    /// Creates the group; any missing intermediate groups are created as well.
+    pub fn create(&self, name: &str) -> Result<Group> {
+        h5lock!({
+            let parent = try_ref_clone!(self.parent);
+            let gcpl = self.build_gcpl()?;
+            let lcpl = make_lcpl()?;
+            let name = to_cstring(name)?;
+            Group::from_id(h5try!(H5Gcreate2(
+                parent.id(),
+                name.as_ptr(),
+                lcpl.id(),
+                gcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
+}
+
+#[cfg(not(feature = "1.12.0"))]
+use hdf5_sys::h5l::H5Lget_info1 as H5Lget_info;
+#[cfg(feature = "1.12.0")]
+use hdf5_sys::h5l::H5Lget_info2 as H5Lget_info;
+
+fn get_link_info(id: hid_t, name: &str) -> Result<H5L_info_t> {
+    let name = to_cstring(name)?;
+    let mut info = H5L_info_t::default();
+    h5call!(H5Lget_info(id, name.as_ptr(), &mut info, H5P_DEFAULT)).and(Ok(info))
 }
 
 #[cfg(test)]
@@ -488,6 +933,93 @@ pub mod tests {
             assert_eq!(file.len(), 2);
             assert_eq!(file.group("bar").unwrap().len(), 1);
             assert_eq!(file.group("/bar/baz").unwrap().len(), 0);
+
+            // all kinds of links are counted, including dangling soft links
+            let group = file.create_group("mixed").unwrap();
+            group.new_dataset::<u8>().create("data").unwrap();
+            group.create_group("child").unwrap();
+            group.link_soft("/missing", "dangling").unwrap();
+            assert_eq!(group.len(), 3);
+            assert!(!group.is_empty());
+            assert_eq!(group.len() as usize, group.member_names().unwrap().len());
+        })
+    }
+
+    #[test]
+    pub fn test_group_builder_link_storage() {
+        use super::group_info;
+        use hdf5_sys::h5g::H5G_storage_type_t::*;
+
+        with_tmp_path(|path| {
+            // compact/dense link storage requires the new group format
+            let file = File::with_options().with_fapl(|p| p.libver_latest()).create(&path).unwrap();
+            let default = file.create_group("default").unwrap();
+            let index = file
+                .new_group_builder()
+                .est_link_info(1000, 12)
+                .link_phase_change(4, 2)
+                .create("a/index")
+                .unwrap();
+            for i in 0..6 {
+                default.create_group(&format!("entry_{:06}", i)).unwrap();
+                index.create_group(&format!("entry_{:06}", i)).unwrap();
+            }
+            assert_eq!(index.len(), 6);
+            let storage = |g: &Group| group_info(g.id()).unwrap().storage_type;
+            assert_eq!(storage(&default), H5G_STORAGE_TYPE_COMPACT);
+            assert_eq!(storage(&index), H5G_STORAGE_TYPE_DENSE);
+            for i in 0..5 {
+                index.unlink(&format!("entry_{:06}", i)).unwrap();
+            }
+            assert_eq!(storage(&index), H5G_STORAGE_TYPE_COMPACT);
+        })
+    }
+
+    #[test]
+    pub fn test_link_creation_order() {
+        use super::{IterationOrder, TraversalOrder};
+        use crate::hl::plist::group_create::{GroupCreate, LinkCreationOrder};
+
+        with_tmp_file(|file| {
+            let gcpl = GroupCreate::build()
+                .link_creation_order(LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED)
+                .finish()
+                .unwrap();
+            let ordered = file.create_group_with_plist("ordered", &gcpl).unwrap();
+            let tracked = file
+                .new_group_builder()
+                .link_creation_order(LinkCreationOrder::TRACKED)
+                .create("tracked")
+                .unwrap();
+            let default = file.create_group("default").unwrap();
+            assert_eq!(
+                ordered.gcpl().unwrap().link_creation_order(),
+                LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED
+            );
+            assert_eq!(default.gcpl().unwrap().link_creation_order(), LinkCreationOrder::empty());
+
+            let names = ["zeta", "alpha", "mu", "beta"];
+            for group in &[&ordered, &tracked, &default] {
+                for name in &names {
+                    group.create_group(name).unwrap();
+                }
+            }
+            let in_order = |group: &Group, order| {
+                group.iter_visit(order, TraversalOrder::Creation, vec![], |_, name, info, names| {
+                    names.push((name.to_owned(), info.creation_order));
+                    true
+                })
+            };
+            let expected: Vec<_> =
+                names.iter().enumerate().map(|(i, &n)| (n.to_owned(), Some(i as i64))).collect();
+            assert_eq!(in_order(&ordered, IterationOrder::Increasing).unwrap(), expected);
+            let reversed: Vec<_> = expected.iter().cloned().rev().collect();
+            assert_eq!(in_order(&ordered, IterationOrder::Decreasing).unwrap(), reversed);
+            // without an index, creation order can still be used while the links are compact
+            assert_eq!(in_order(&tracked, IterationOrder::Increasing).unwrap(), expected);
+            assert!(in_order(&default, IterationOrder::Increasing).is_err());
+            // iteration by name is unaffected
+            assert_eq!(ordered.member_names().unwrap(), vec!["alpha", "beta", "mu", "zeta"]);
         })
     }
 
@@ -522,6 +1054,29 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_link_object() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().shape(10).create(None).unwrap();
+            ds.write(&(0..10).collect::<Vec<_>>()).unwrap();
+            assert_eq!(ds.num_links().unwrap(), 0);
+            assert!(file.is_empty());
+
+            file.link_object(&ds, "a/b/data").unwrap();
+            assert_eq!(ds.num_links().unwrap(), 1);
+            let data = file.dataset("a/b/data").unwrap();
+            assert_eq!(data.read_raw::<i32>().unwrap(), (0..10).collect::<Vec<_>>());
+
+            // linking objects which already have a path creates another hard link
+            let group = file.create_group("c").unwrap();
+            group.link_object(&data, "copy").unwrap();
+            assert_eq!(ds.num_links().unwrap(), 2);
+            let copy = file.dataset("c/copy").unwrap();
+            assert_eq!(copy.loc_info().unwrap().token, data.loc_info().unwrap().token);
+            assert_err!(group.link_object(&data, "copy"), "already exists");
+        })
+    }
+
     #[test]
     pub fn test_link_soft() {
         with_tmp_file(|file| {
@@ -542,6 +1097,86 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_link_target() {
+        use super::LinkTarget;
+
+        with_tmp_file(|file| {
+            file.create_group("a/b").unwrap();
+            file.link_soft("/a/b", "a/soft").unwrap();
+            file.link_soft("/missing/path", "a/dangling").unwrap();
+            file.link_external("missing.h5", "/x/y", "a/ext").unwrap();
+            let a = file.group("a").unwrap();
+
+            assert_eq!(a.link_info("b").unwrap().link_type, LinkType::Hard);
+            assert_eq!(a.link_target("b").unwrap(), LinkTarget::Hard);
+            assert_eq!(a.link_info("soft").unwrap().link_type, LinkType::Soft);
+            assert_eq!(a.link_target("soft").unwrap(), LinkTarget::Soft("/a/b".into()));
+            assert_eq!(file.link_info("/a/dangling").unwrap().link_type, LinkType::Soft);
+            assert_eq!(
+                file.link_target("/a/dangling").unwrap(),
+                LinkTarget::Soft("/missing/path".into())
+            );
+            assert_eq!(a.link_info("ext").unwrap().link_type, LinkType::External);
+            assert_eq!(
+                a.link_target("ext").unwrap(),
+                LinkTarget::External { filename: "missing.h5".into(), path: "/x/y".into() }
+            );
+            assert!(a.link_info("missing").is_err());
+            assert!(a.link_target("missing").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_new_dataset_from() {
+        with_tmp_file(|file| {
+            let arr = ndarray::Array3::from_shape_fn((2, 3, 4), |(i, j, k)| {
+                (i * 100 + j * 10 + k) as i32
+            });
+            let ds = file.new_dataset_from("a/b/data", &arr).unwrap();
+            assert_eq!(ds.shape(), vec![2, 3, 4]);
+            assert!(ds.dtype().unwrap().is::<i32>());
+            assert_eq!(ds.read_dyn::<i32>().unwrap(), arr.clone().into_dyn());
+            let t = arr.view().reversed_axes();
+            let ds = file.group("a").unwrap().new_dataset_from("transposed", t).unwrap();
+            assert_eq!(ds.shape(), vec![4, 3, 2]);
+            assert_eq!(
+                file.dataset("a/transposed").unwrap().read_dyn::<i32>().unwrap(),
+                t.into_dyn()
+            );
+            assert!(file.new_dataset_from("a/b/data", &arr).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_dataset_paths() {
+        with_tmp_file(|file| {
+            file.new_dataset::<u8>().create("x").unwrap();
+            file.new_dataset::<u8>().create("a/y").unwrap();
+            file.new_dataset::<u8>().create("a/b/z").unwrap();
+            file.create_group("c").unwrap();
+            file.link_hard("/a/b/z", "c/alias").unwrap();
+            file.link_soft("/a/y", "c/soft").unwrap();
+            file.link_soft("/missing", "c/dangling").unwrap();
+            file.create_group("d").unwrap();
+            file.link_soft("/d", "d/cycle").unwrap();
+            file.new_dataset::<u8>().create("e/w").unwrap();
+            file.link_soft("/e", "c/e").unwrap();
+            file.link_soft("/x", "a/b/to_x").unwrap();
+
+            // the alias is found first since the traversal is breadth-first; called on the
+            // file, this lists all datasets starting from the root group
+            let paths = file.dataset_paths(false).unwrap();
+            assert_eq!(paths, ["/x", "/a/y", "/c/alias", "/e/w"]);
+            assert_eq!(file.dataset_paths(true).unwrap(), paths);
+
+            let a = file.group("a").unwrap();
+            assert_eq!(a.dataset_paths(false).unwrap(), ["/a/y", "/a/b/z"]);
+            assert_eq!(a.dataset_paths(true).unwrap(), ["/a/y", "/a/b/z", "/a/b/to_x"]);
+            assert!(file.group("d").unwrap().dataset_paths(true).unwrap().is_empty());
+        })
+    }
+
     #[test]
     pub fn test_link_exists() {
         with_tmp_file(|file| {
@@ -566,6 +1201,28 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_open_with_lapl() {
+        use crate::plist::LinkAccess;
+
+        with_tmp_file(|file| {
+            file.new_dataset::<u8>().create("data").unwrap();
+            file.link_soft("/data", "link0").unwrap();
+            for i in 1..20 {
+                file.link_soft(&format!("/link{}", i - 1), &format!("link{}", i)).unwrap();
+            }
+            assert!(file.dataset("link10").is_ok());
+            assert_err!(file.dataset("link19"), "too many links");
+
+            let lapl = LinkAccess::build().nlinks(16).finish().unwrap();
+            assert_err!(file.open_with_lapl("link19", &lapl), "too many links");
+            let lapl = LinkAccess::build().nlinks(32).finish().unwrap();
+            let ds: Dataset = file.open_with_lapl("link19", &lapl).unwrap().cast().unwrap();
+            assert_eq!(ds.name(), "/link19");
+            assert!(file.open_with_lapl("missing", &lapl).is_err());
+        })
+    }
+
     #[test]
     pub fn test_relink() {
         with_tmp_file(|file| {
@@ -586,6 +1243,29 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_move_link() {
+        with_tmp_file(|file| {
+            let a = file.create_group("a").unwrap();
+            let b = file.create_group("b").unwrap();
+            a.new_dataset::<u32>().shape(3).create("x").unwrap().write(&[1, 2, 3]).unwrap();
+            assert_err!(a.move_link("x", &b, "c/x"), "unable to move link: component not found");
+            b.create_group("c").unwrap();
+            a.move_link("x", &b, "c/y").unwrap();
+            assert!(!a.link_exists("x"));
+            assert!(file.dataset("/a/x").is_err());
+            assert_eq!(file.dataset("/b/c/y").unwrap().read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+            assert_err!(a.move_link("x", &b, "z"), "unable to move link: name doesn't exist");
+
+            // moving groups works the same way, with relative names resolved from each group
+            file.move_link("b/c", &a, "d").unwrap();
+            assert_eq!(file.dataset("a/d/y").unwrap().name(), "/a/d/y");
+            with_tmp_file(|other| {
+                assert!(a.move_link("d", &other, "d").is_err());
+            });
+        })
+    }
+
     #[test]
     pub fn test_unlink() {
         with_tmp_file(|file| {
@@ -608,6 +1288,93 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_new_dataset_from_iter() {
+        with_tmp_file(|file| {
+            // large enough to be written in multiple batches
+            let ds = file.new_dataset_from_iter("1d", 300_000, 0..300_000_u64).unwrap();
+            assert_eq!(ds.shape(), vec![300_000]);
+            assert_eq!(ds.read_raw::<u64>().unwrap(), (0..300_000).collect::<Vec<_>>());
+
+            let ds = file.new_dataset_from_iter("3d", (7, 3, 2), (0..42).map(f64::from)).unwrap();
+            let expected =
+                ndarray::Array::from_shape_fn((7, 3, 2), |(i, j, k)| (i * 6 + j * 2 + k) as f64);
+            assert_eq!(ds.read::<f64, ndarray::Ix3>().unwrap(), expected);
+
+            let ds = file.new_dataset_from_iter("scalar", (), vec![true]).unwrap();
+            assert!(ds.read_scalar::<bool>().unwrap());
+            file.new_dataset_from_iter("empty", (0, 5), std::iter::empty::<i8>()).unwrap();
+
+            assert_err!(
+                file.new_dataset_from_iter("short", (4, 3), 0..10_u8),
+                "Iterator produced 10 elements, expected 12"
+            );
+            assert_err!(
+                file.new_dataset_from_iter("long", 10, 0..11_u8),
+                "Iterator produced more than 10 elements"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_require_group() {
+        with_tmp_file(|file| {
+            let group = file.require_group("a/b").unwrap();
+            assert_eq!(group.name(), "/a/b");
+            group.create_group("c").unwrap();
+            let group = file.require_group("a/b").unwrap();
+            assert_eq!(group.member_names().unwrap(), vec!["c"]);
+            file.new_dataset::<i32>().create("ds").unwrap();
+            assert!(file.require_group("ds").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_require_dataset() {
+        with_tmp_file(|file| {
+            let ds = file.require_dataset::<f32>("a/ds", (10, 20)).unwrap();
+            assert_eq!(ds.name(), "/a/ds");
+            ds.write(&ndarray::Array2::<f32>::ones((10, 20))).unwrap();
+            let ds = file.require_dataset::<f32>("a/ds", (10, 20)).unwrap();
+            assert_eq!(ds.read_2d::<f32>().unwrap(), ndarray::Array2::<f32>::ones((10, 20)));
+            file.require_dataset::<f64>("a/ds", (10, 20)).unwrap();
+            assert_err!(
+                file.require_dataset::<f32>("a/ds", (20, 10)),
+                "shape mismatch for dataset \"a/ds\": expected [20, 10], got [10, 20]"
+            );
+            assert_err!(
+                file.require_dataset::<crate::types::VarLenUnicode>("a/ds", (10, 20)),
+                "datatype of dataset \"a/ds\" is not convertible to"
+            );
+            file.create_group("g").unwrap();
+            assert!(file.require_dataset::<f32>("g", (10, 20)).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_create_dataset_like() {
+        with_tmp_file(|file| {
+            use crate::dataset::Layout;
+            use crate::filters::Filter;
+
+            let arr = (0..200).map(|x| x as f32).collect::<Vec<_>>();
+            let src =
+                file.new_dataset::<f32>().chunk(50).deflate(4).shape(200..).create("src").unwrap();
+            src.write(&arr).unwrap();
+
+            let dst = file.create_dataset_like("a/dst", &src).unwrap();
+            assert_eq!(dst.name(), "/a/dst");
+            assert_eq!(dst.dtype().unwrap().to_descriptor().unwrap(), f32::type_descriptor());
+            assert_eq!(dst.shape(), vec![200]);
+            assert_eq!(dst.space().unwrap().maxdims(), vec![None]);
+            assert_eq!(dst.layout(), Layout::Chunked);
+            assert_eq!(dst.chunk(), Some(vec![50]));
+            assert_eq!(dst.filters(), vec![Filter::deflate(4)]);
+            assert!(src.storage_size() > 0);
+            assert_eq!(dst.storage_size(), 0);
+        })
+    }
+
     #[test]
     pub fn test_get_member_names() {
         with_tmp_file(|file| {
@@ -624,6 +1391,41 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(feature = "1.8.17")]
+    pub fn test_dataset_with_dapl() {
+        use crate::plist::DatasetAccess;
+        use std::fs;
+
+        with_tmp_dir(|dir| {
+            let path = dir.join("foo.h5");
+            let data = (0..100).collect::<Vec<i32>>();
+            {
+                let file = File::create(&path).unwrap();
+                let ds = file
+                    .new_dataset::<i32>()
+                    .external("data.bin", 0, 400)
+                    .with_dapl(|p| p.efile_prefix("${ORIGIN}"))
+                    .shape(100)
+                    .create("data")
+                    .unwrap();
+                ds.write(&data).unwrap();
+            }
+            assert!(dir.join("data.bin").exists());
+            fs::create_dir(dir.join("ext")).unwrap();
+            fs::rename(dir.join("data.bin"), dir.join("ext").join("data.bin")).unwrap();
+
+            let file = File::open(&path).unwrap();
+            let dapl = DatasetAccess::build().efile_prefix("${ORIGIN}").finish().unwrap();
+            assert!(file.dataset_with_dapl("data", &dapl).unwrap().read_raw::<i32>().is_err());
+            let dapl = DatasetAccess::build().efile_prefix("${ORIGIN}/ext").finish().unwrap();
+            let ds = file.dataset_with_dapl("data", &dapl).unwrap();
+            assert_eq!(ds.dapl().unwrap().efile_prefix(), "${ORIGIN}/ext");
+            assert_eq!(ds.read_raw::<i32>().unwrap(), data);
+            assert!(file.dataset_with_dapl("missing", &dapl).is_err());
+        })
+    }
+
     #[test]
     pub fn test_external_link() {
         with_tmp_dir(|dir| {
@@ -648,6 +1450,37 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_for_each_link() {
+        with_tmp_file(|file| {
+            for i in 0..100 {
+                file.create_group(&format!("g{:03}", i)).unwrap();
+            }
+            let mut visited = vec![];
+            file.for_each_link(|index, name| {
+                visited.push((index, name.to_owned()));
+                if index < 2 {
+                    IterAction::Continue
+                } else {
+                    IterAction::Stop
+                }
+            })
+            .unwrap();
+            assert_eq!(
+                visited,
+                vec![(0, "g000".to_owned()), (1, "g001".to_owned()), (2, "g002".to_owned())]
+            );
+
+            let mut count = 0;
+            file.for_each_link(|_, _| {
+                count += 1;
+                IterAction::Continue
+            })
+            .unwrap();
+            assert_eq!(count, 100);
+        })
+    }
+
     #[test]
     pub fn test_iterators() {
         with_tmp_file(|file| {
@@ -675,4 +1508,24 @@ pub mod tests {
             }
         })
     }
+
+    #[test]
+    pub fn test_iterators_skip_soft_links() {
+        with_tmp_file(|file| {
+            let group = file.create_group("g").unwrap();
+            group.new_dataset::<u8>().create("x").unwrap();
+            group.new_dataset::<u8>().create("y").unwrap();
+            group.create_group("sub").unwrap();
+            group.link_soft("/g/x", "soft").unwrap();
+            group.link_soft("/g/missing", "dangling").unwrap();
+            assert_eq!(group.len(), 5);
+
+            let mut names: Vec<_> = group.datasets().unwrap().iter().map(|ds| ds.name()).collect();
+            names.sort();
+            assert_eq!(names, vec!["/g/x", "/g/y"]);
+            let names: Vec<_> = group.groups().unwrap().iter().map(|g| g.name()).collect();
+            assert_eq!(names, vec!["/g/sub"]);
+            assert!(group.named_datatypes().unwrap().is_empty());
+        })
+    }
 }
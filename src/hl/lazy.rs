@@ -0,0 +1,186 @@
+use std::convert::TryInto;
+use std::fmt::{self, Debug};
+
+use ndarray::{Array, ArrayD};
+
+use crate::internal_prelude::*;
+
+/// An axis of the dataset as seen through a lazy view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ViewAxis {
+    /// The axis is indexed and is not part of the view.
+    Index(Ix),
+    /// `count` elements of the axis, starting at `start`, every `step` elements.
+    Slice { start: Ix, step: Ix, count: Ix },
+}
+
+/// A lazy view of a region of a dataset which defers reading until it's read explicitly.
+///
+/// Views are created via [`Dataset::lazy()`] and narrowed down via [`slice()`](Self::slice),
+/// where the indices are relative to the view itself (like when slicing array views in
+/// `ndarray`). Chained slices are combined into a single hyperslab selection of the dataset,
+/// so no data is read until calling [`read()`](Self::read).
+///
+/// Note that the view doesn't track changes of the shape of the dataset; if the dataset is
+/// shrunk, reading a view that is no longer within its bounds returns an error.
+#[derive(Clone)]
+pub struct LazyDataset {
+    ds: Dataset,
+    axes: Vec<ViewAxis>,
+}
+
+impl Debug for LazyDataset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyDataset")
+            .field("dataset", &self.ds)
+            .field("selection", &self.selection().to_string())
+            .finish()
+    }
+}
+
+impl LazyDataset {
+    /// Creates a lazy view of the whole dataset.
+    pub fn new(ds: &Dataset) -> Result<Self> {
+        let axes = ds
+            .get_shape()?
+            .into_iter()
+            .map(|count| ViewAxis::Slice { start: 0, step: 1, count })
+            .collect();
+        Ok(Self { ds: ds.clone(), axes })
+    }
+
+    /// Returns the underlying dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+
+    /// Returns the shape of the view (which doesn't include the indexed axes of the dataset).
+    pub fn shape(&self) -> Vec<Ix> {
+        self.axes
+            .iter()
+            .filter_map(|axis| match *axis {
+                ViewAxis::Slice { count, .. } => Some(count),
+                ViewAxis::Index(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the number of dimensions of the view.
+    pub fn ndim(&self) -> usize {
+        self.shape().len()
+    }
+
+    /// Returns the number of elements in the view.
+    pub fn size(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    /// Returns a new lazy view of the given region of this view, without reading any data.
+    ///
+    /// The selection (e.g. created via the `s![]` macro) must have one slice or index per
+    /// axis of this view and must be within its bounds; indexed axes are dropped from the
+    /// resulting view, and blocks are not supported.
+    pub fn slice<S>(&self, selection: S) -> Result<Self>
+    where
+        S: TryInto<Hyperslab>,
+        Error: From<S::Error>,
+    {
+        let hyper = selection.try_into()?;
+        let raw = hyper.clone().into_raw(self.shape())?;
+        let mut slices = hyper.iter().zip(raw.iter());
+        let axes = self
+            .axes
+            .iter()
+            .map(|&axis| match axis {
+                ViewAxis::Index(_) => Ok(axis),
+                ViewAxis::Slice { start, step, .. } => {
+                    let (slice, raw) = slices.next().ok_or("Selection ndim mismatch")?;
+                    ensure!(raw.block == 1, "Block selections are not supported in lazy views");
+                    let start = start + raw.start * step;
+                    Ok(if slice.is_index() {
+                        ViewAxis::Index(start)
+                    } else {
+                        let count = raw.count.ok_or("Unlimited slices are not supported")?;
+                        ViewAxis::Slice { start, step: step * raw.step, count }
+                    })
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { ds: self.ds.clone(), axes })
+    }
+
+    /// Returns the selection of the view in the coordinates of the dataset.
+    pub fn selection(&self) -> Hyperslab {
+        let dims: Vec<_> = self
+            .axes
+            .iter()
+            .map(|axis| match *axis {
+                ViewAxis::Index(index) => SliceOrIndex::Index(index),
+                ViewAxis::Slice { start, step, count } => {
+                    SliceOrIndex::SliceCount { start, step, count, block: 1 }
+                }
+            })
+            .collect();
+        dims.into()
+    }
+
+    /// Reads the view into an n-dimensional array, using a single hyperslab selection.
+    ///
+    /// If the array has a fixed number of dimensions, it must match the dimensionality
+    /// of the view.
+    pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
+        self.ds.read_slice(self.selection())
+    }
+
+    /// Reads the view into an array with dynamic number of dimensions.
+    pub fn read_dyn<T: H5Type>(&self) -> Result<ArrayD<T>> {
+        self.read()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{s, Array3, Ix2};
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_chained_slices() {
+        with_tmp_file(|file| {
+            let arr = Array3::from_shape_fn((6, 10, 8), |(i, j, k)| (i * 100 + j * 10 + k) as i32);
+            let ds = file.new_dataset_builder().with_data(&arr).create("x").unwrap();
+            let view = ds.lazy().unwrap();
+            assert_eq!(view.shape(), vec![6, 10, 8]);
+            assert_eq!(view.read_dyn::<i32>().unwrap(), arr.clone().into_dyn());
+
+            let roi = view.slice(s![1..5, 2..;2, ..]).unwrap().slice(s![1.., 1..4, 3]).unwrap();
+            assert_eq!(roi.shape(), vec![3, 3]);
+            assert_eq!(roi.size(), 9);
+            // both slices are combined into a single hyperslab of the dataset
+            let expected = Hyperslab::try_new(s![2..5, 4..10;2, 3]).unwrap();
+            assert_eq!(roi.selection(), expected);
+            let direct = ds.read_slice::<i32, _, Ix2>(s![2..5, 4..10;2, 3]).unwrap();
+            assert_eq!(roi.read::<i32, Ix2>().unwrap(), direct);
+            assert_eq!(direct, arr.slice(s![1..5, 2..;2, ..]).slice(s![1.., 1..4, 3]));
+
+            // steps compose, and indexed axes are skipped by further slices
+            let view = view.slice(s![.., 1..;3, 5]).unwrap();
+            assert_eq!(view.shape(), vec![6, 3]);
+            let view = view.slice(s![..;2, 1..]).unwrap();
+            assert_eq!(view.selection(), Hyperslab::try_new(s![..6;2, 4..10;3, 5]).unwrap());
+            assert_eq!(view.read_dyn::<i32>().unwrap(), arr.slice(s![..;2, 4..;3, 5]).into_dyn());
+            let elem = view.slice(s![2, 0]).unwrap();
+            assert_eq!(elem.ndim(), 0);
+            assert_eq!(elem.read_dyn::<i32>().unwrap().into_raw_vec(), vec![445]);
+
+            // selections are checked against the shape of the view
+            assert!(view.slice(s![.., .., 0]).is_err());
+            assert!(view.slice(s![4.., ..]).is_err());
+            assert!(ds
+                .lazy()
+                .unwrap()
+                .slice(Hyperslab::new((.., .., 0..4)).set_block(2, 2).unwrap())
+                .is_err());
+        })
+    }
+}
@@ -1,8 +1,13 @@
 use std::fmt::{self, Debug};
-use std::mem::MaybeUninit;
+use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
 use std::ptr;
 
+use ndarray::aview0;
+
+#[cfg(not(feature = "1.12.0"))]
+#[allow(deprecated)]
+use hdf5_sys::h5d::H5Dvlen_reclaim;
 #[allow(deprecated)]
 use hdf5_sys::h5o::H5Oset_comment;
 #[cfg(feature = "1.12.0")]
@@ -15,15 +20,19 @@ use hdf5_sys::h5o::{H5Oget_info1, H5Oget_info_by_name1};
 use hdf5_sys::h5o::{H5Oget_info2, H5Oget_info_by_name2};
 #[cfg(feature = "1.10.3")]
 use hdf5_sys::h5o::{H5O_INFO_BASIC, H5O_INFO_NUM_ATTRS, H5O_INFO_TIME};
+#[cfg(feature = "1.12.0")]
+use hdf5_sys::h5t::H5Treclaim;
 #[cfg(not(feature = "1.12.0"))]
 use hdf5_sys::{h5::haddr_t, h5o::H5O_info1_t, h5o::H5Oopen_by_addr};
 use hdf5_sys::{
-    h5a::H5Aopen,
+    h5a::{H5Acreate2, H5Aexists, H5Aopen, H5Aread, H5Awrite},
     h5f::H5Fget_name,
     h5i::{H5Iget_file_id, H5Iget_name},
     h5o::{H5O_type_t, H5Oget_comment},
 };
 
+use hdf5_types::{OwnedDynValue, TypeDescriptor};
+
 use crate::internal_prelude::*;
 
 use super::attribute::AttributeBuilderEmpty;
@@ -121,10 +130,110 @@ impl Location {
         Attribute::from_id(h5try!(H5Aopen(self.id(), name.as_ptr(), H5P_DEFAULT)))
     }
 
+    /// Reads a 1-D attribute into a vector (see [`Attribute::read_vec`]).
+    pub fn attr_vec<T: H5Type>(&self, name: &str) -> Result<Vec<T>> {
+        self.attr(name)?.read_vec()
+    }
+
     pub fn attr_names(&self) -> Result<Vec<String>> {
         Attribute::attr_names(self)
     }
 
+    /// Reads all attributes as `(name, value)` pairs of dynamically typed values, in
+    /// increasing name order.
+    ///
+    /// Scalar attributes are returned as single values; attributes of any other shape are
+    /// flattened in row-major order into a fixed-size array value (so that, for example,
+    /// an attribute of shape `(2, 3)` is represented as an array of 6 elements).
+    pub fn attrs_map(&self) -> Result<Vec<(String, OwnedDynValue)>> {
+        let mut map = Vec::new();
+        for name in self.attr_names()? {
+            let attr = self.attr(&name)?;
+            let mut values = attr.read_raw_dyn()?;
+            let value = if attr.is_scalar() {
+                values.pop().ok_or_else(|| format!("Failed to read attribute {:?}", name))?
+            } else {
+                let desc = attr.dtype()?.to_descriptor()?.to_c_repr();
+                let mut buf = Vec::with_capacity(values.len() * desc.size());
+                let len = values.len();
+                for value in values {
+                    // the variable-length data (if any) is now owned by the array value
+                    buf.extend_from_slice(unsafe { value.get_buf() });
+                    mem::forget(value);
+                }
+                let desc = TypeDescriptor::FixedArray(Box::new(desc), len);
+                unsafe { OwnedDynValue::from_raw(desc, buf.into_boxed_slice()) }
+            };
+            map.push((name, value));
+        }
+        Ok(map)
+    }
+
+    /// Creates a scalar attribute with the given value unless an attribute with the same
+    /// name already exists, returning `true` if the attribute has been created.
+    ///
+    /// An existing attribute is left intact regardless of its type and value, which is
+    /// useful for stamping metadata (e.g. a `created_by` attribute) in idempotent code.
+    pub fn set_attr_if_absent<T: H5Type>(&self, name: &str, value: &T) -> Result<bool> {
+        let c_name = to_cstring(name)?;
+        h5lock!({
+            if h5call!(H5Aexists(self.id(), c_name.as_ptr()))? > 0 {
+                return Ok(false);
+            }
+            self.new_attr_builder().with_data(aview0(value)).create(name)?;
+            Ok(true)
+        })
+    }
+
+    /// Visits the names of all attributes in increasing name order, passing the index of
+    /// each attribute along with its name; returning [`IterAction::Stop`] stops the
+    /// iteration early.
+    pub fn for_each_attr<F>(&self, op: F) -> Result<()>
+    where
+        F: FnMut(usize, &str) -> IterAction,
+    {
+        Attribute::for_each_attr(self, op)
+    }
+
+    /// Copies all attributes of `src` to this object, preserving their names, datatypes,
+    /// shapes and values.
+    ///
+    /// Fails if an attribute with the same name already exists on this object.
+    pub fn copy_attrs_from(&self, src: &Self) -> Result<()> {
+        for name in src.attr_names()? {
+            let attr = src.attr(&name)?;
+            let (dtype, space) = (attr.dtype()?, attr.space()?);
+            let mem_dtype = Datatype::from_descriptor(&dtype.to_descriptor()?)?;
+            let name = to_cstring(name.as_str())?;
+            h5lock!({
+                let copy = Attribute::from_id(h5try!(H5Acreate2(
+                    self.id(),
+                    name.as_ptr(),
+                    dtype.id(),
+                    space.id(),
+                    H5P_DEFAULT,
+                    H5P_DEFAULT,
+                )))?;
+                let size = mem_dtype.size() * space.size();
+                if size > 0 {
+                    let mut buf = vec![0_u8; size];
+                    let buf_ptr: *mut c_void = buf.as_mut_ptr().cast();
+                    h5try!(H5Aread(attr.id(), mem_dtype.id(), buf_ptr));
+                    let written = h5call!(H5Awrite(copy.id(), mem_dtype.id(), buf_ptr));
+                    // variable-length data in the buffer has been allocated by HDF5 when reading
+                    #[cfg(feature = "1.12.0")]
+                    h5try!(H5Treclaim(mem_dtype.id(), space.id(), H5P_DEFAULT, buf_ptr));
+                    #[cfg(not(feature = "1.12.0"))]
+                    #[allow(deprecated)]
+                    h5try!(H5Dvlen_reclaim(mem_dtype.id(), space.id(), H5P_DEFAULT, buf_ptr));
+                    written?;
+                }
+                Ok::<_, Error>(())
+            })?;
+        }
+        Ok(())
+    }
+
     pub fn loc_info(&self) -> Result<LocationInfo> {
         H5O_get_info(self.id(), true)
     }
@@ -133,6 +242,12 @@ impl Location {
         Ok(H5O_get_info(self.id(), false)?.loc_type)
     }
 
+    /// Returns the number of hard links pointing to this object; if it's greater than one,
+    /// the object is shared between multiple paths in the file.
+    pub fn num_links(&self) -> Result<usize> {
+        Ok(H5O_get_info(self.id(), false)?.num_links)
+    }
+
     pub fn loc_info_by_name(&self, name: &str) -> Result<LocationInfo> {
         let name = to_cstring(name)?;
         H5O_get_info_by_name(self.id(), name.as_ptr(), true)
@@ -148,7 +263,7 @@ impl Location {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct LocationToken(
     #[cfg(not(feature = "1.12.0"))] haddr_t,
     #[cfg(feature = "1.12.0")] H5O_token_t,
@@ -417,4 +532,144 @@ pub mod tests {
             assert!(file.loc_info_by_name("gibberish").is_err());
         })
     }
+
+    #[test]
+    pub fn test_copy_attrs_from() {
+        use crate::types::{FixedAscii, VarLenUnicode};
+        use ndarray::arr2;
+        use std::str::FromStr;
+        with_tmp_file(|file| {
+            let src = file.new_dataset::<f32>().shape(5).create("src").unwrap();
+            let unicode = VarLenUnicode::from_str("température").unwrap();
+            let ascii = FixedAscii::<8>::from_ascii(b"meters").unwrap();
+            let matrix = arr2(&[[1_u16, 2, 3], [4, 5, 6]]);
+            src.new_attr::<i64>().create("scalar").unwrap().write_scalar(&-42_i64).unwrap();
+            src.new_attr_builder().with_data(&matrix).create("matrix").unwrap();
+            src.new_attr::<VarLenUnicode>().create("name").unwrap().write_scalar(&unicode).unwrap();
+            src.new_attr::<FixedAscii<8>>().create("units").unwrap().write_scalar(&ascii).unwrap();
+
+            let dst = file.new_dataset::<f32>().shape(5).create("dst").unwrap();
+            dst.copy_attrs_from(&src).unwrap();
+            assert_eq!(dst.attr_names().unwrap(), src.attr_names().unwrap());
+            for name in src.attr_names().unwrap() {
+                let (a, b) = (src.attr(&name).unwrap(), dst.attr(&name).unwrap());
+                assert_eq!(a.dtype().unwrap(), b.dtype().unwrap());
+                assert_eq!(a.shape(), b.shape());
+            }
+            assert_eq!(dst.attr("scalar").unwrap().read_scalar::<i64>().unwrap(), -42);
+            assert_eq!(dst.attr("matrix").unwrap().read_2d::<u16>().unwrap(), matrix);
+            assert_eq!(dst.attr("name").unwrap().read_scalar::<VarLenUnicode>().unwrap(), unicode);
+            assert_eq!(dst.attr("units").unwrap().read_scalar::<FixedAscii<8>>().unwrap(), ascii);
+
+            assert!(dst.copy_attrs_from(&src).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_set_attr_if_absent() {
+        use crate::types::VarLenUnicode;
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f32>().shape(5).create("ds").unwrap();
+            let first: VarLenUnicode = "pipeline v1".parse().unwrap();
+            let second: VarLenUnicode = "pipeline v2".parse().unwrap();
+            assert!(ds.set_attr_if_absent("created_by", &first).unwrap());
+            assert!(!ds.set_attr_if_absent("created_by", &second).unwrap());
+            let attr = ds.attr("created_by").unwrap();
+            assert!(attr.is_scalar());
+            assert_eq!(attr.read_scalar::<VarLenUnicode>().unwrap(), first);
+
+            // existing attributes of other types are left intact as well
+            assert!(file.set_attr_if_absent("version", &3_u32).unwrap());
+            assert!(!file.set_attr_if_absent("version", &4.5_f64).unwrap());
+            assert_eq!(file.attr("version").unwrap().read_scalar::<u32>().unwrap(), 3);
+            assert_eq!(file.attr_names().unwrap(), vec!["version"]);
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "1.10.2")]
+    pub fn test_timestamps() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        with_tmp_path(|path| {
+            let file = File::with_options().with_fapl(|p| p.libver_v110()).create(path).unwrap();
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+            let tracked = file
+                .new_dataset_builder()
+                .obj_track_times(true)
+                .with_data(&[1, 2, 3])
+                .create("tracked")
+                .unwrap();
+            let info = tracked.loc_info().unwrap();
+            // timestamps are stored with a resolution of one second
+            assert!((info.mtime - now).abs() <= 60, "mtime: {}, now: {}", info.mtime, now);
+            assert!(info.btime > 0 && info.btime <= info.mtime);
+
+            let untracked = file
+                .new_dataset_builder()
+                .obj_track_times(false)
+                .with_data(&[1, 2, 3])
+                .create("untracked")
+                .unwrap();
+            let info = untracked.loc_info().unwrap();
+            assert_eq!((info.atime, info.mtime, info.ctime, info.btime), (0, 0, 0, 0));
+        })
+    }
+
+    #[test]
+    pub fn test_attrs_map() {
+        use crate::types::{OwnedDynValue, VarLenUnicode};
+        use ndarray::arr2;
+
+        with_tmp_file(|file| {
+            assert!(file.attrs_map().unwrap().is_empty());
+            let ds = file.new_dataset::<u8>().create("ds").unwrap();
+            let name: VarLenUnicode = "temperature".parse().unwrap();
+            ds.new_attr::<i32>().create("int").unwrap().write_scalar(&3).unwrap();
+            ds.new_attr::<f64>().create("float").unwrap().write_scalar(&2.5).unwrap();
+            ds.new_attr::<VarLenUnicode>().create("name").unwrap().write_scalar(&name).unwrap();
+            ds.new_attr_builder().with_data(&[1.0_f32, 2.0, 3.0]).create("values").unwrap();
+            ds.new_attr_builder().with_data(&arr2(&[[1_i16, 2], [3, 4]])).create("grid").unwrap();
+
+            let map = ds.attrs_map().unwrap();
+            let names = map.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+            assert_eq!(names, vec!["float", "grid", "int", "name", "values"]);
+            assert_eq!(map[0].1, OwnedDynValue::new(2.5_f64));
+            assert_eq!(map[1].1, OwnedDynValue::new([1_i16, 2, 3, 4]));
+            assert_eq!(map[2].1, OwnedDynValue::new(3_i32));
+            assert_eq!(map[3].1, OwnedDynValue::new(name));
+            assert_eq!(map[4].1, OwnedDynValue::new([1.0_f32, 2.0, 3.0]));
+            assert_eq!(format!("{}", map[4].1), "[1.0, 2.0, 3.0]");
+        })
+    }
+
+    #[test]
+    pub fn test_num_links() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().create("ds").unwrap();
+            file.new_dataset::<i32>().create("other").unwrap();
+            assert_eq!(ds.num_links().unwrap(), 1);
+            file.link_hard("ds", "a").unwrap();
+            file.link_hard("ds", "b").unwrap();
+            file.link_soft("ds", "c").unwrap();
+            assert_eq!(ds.num_links().unwrap(), 3);
+            assert_eq!(file.dataset("b").unwrap().num_links().unwrap(), 3);
+            assert_eq!(file.num_links().unwrap(), 1);
+
+            // detect shared objects while visiting a group
+            let shared = file
+                .iter_visit_default(vec![], |group, name, info, shared| {
+                    if info.link_type == LinkType::Hard {
+                        let info = group.loc_info_by_name(name).unwrap();
+                        if info.num_links > 1 && !shared.contains(&info.token) {
+                            shared.push(info.token);
+                        }
+                    }
+                    true
+                })
+                .unwrap();
+            assert_eq!(shared.len(), 1);
+            assert_eq!(file.open_by_token(shared[0]).unwrap().num_links().unwrap(), 3);
+        })
+    }
 }
@@ -29,6 +29,12 @@ impl Debug for Object {
 }
 
 impl Object {
+    /// Returns the raw identifier of the object, e.g. for passing it to C code using HDF5.
+    ///
+    /// The identifier is borrowed: it remains owned by this wrapper and is only valid while
+    /// the wrapper (or one of its clones) is alive, so it must not be closed by the caller.
+    /// To hand out an identifier that outlives the wrapper, increment its reference count
+    /// first (`H5Iinc_ref`); the receiver then owns that extra reference.
     pub fn id(&self) -> hid_t {
         self.0.id()
     }
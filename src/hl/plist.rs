@@ -14,8 +14,11 @@ use crate::internal_prelude::*;
 pub mod common;
 pub mod dataset_access;
 pub mod dataset_create;
+pub mod dataset_transfer;
 pub mod file_access;
 pub mod file_create;
+pub mod group_create;
+pub mod link_access;
 pub mod link_create;
 
 /// Represents the HDF5 property list.
@@ -90,10 +90,15 @@ impl Clone for DatasetAccess {
     }
 }
 
+/// Determines the extent of a virtual dataset with unlimited mappings whose
+/// source files or datasets are partially missing.
 #[cfg(feature = "1.10.0")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VirtualView {
+    /// The extent ends before the first missing source.
     FirstMissing,
+    /// The extent includes all available sources; the data of missing sources
+    /// in between is filled with the fill value of the virtual dataset.
     LastAvailable,
 }
 
@@ -170,18 +175,31 @@ impl DatasetAccessBuilder {
     }
 
     #[cfg(feature = "1.8.17")]
+    /// Sets the prefix prepended to the relative names of external storage files
+    /// of the dataset when they are opened.
+    ///
+    /// If the prefix starts with `${ORIGIN}`, this token is replaced with the directory
+    /// containing the HDF5 file, which allows relocating the file together with its
+    /// external files. If the `HDF5_EXTFILE_PREFIX` environment variable is set, it takes
+    /// precedence over this setting.
     pub fn efile_prefix(&mut self, prefix: &str) -> &mut Self {
         self.efile_prefix = Some(prefix.into());
         self
     }
 
     #[cfg(feature = "1.10.0")]
+    /// Sets how the extent of an unlimited virtual dataset is determined when some of
+    /// its source files or datasets are missing (`LastAvailable` by default).
     pub fn virtual_view(&mut self, view: VirtualView) -> &mut Self {
         self.virtual_view = Some(view);
         self
     }
 
     #[cfg(feature = "1.10.0")]
+    /// Sets the maximum number of consecutive missing source files or datasets with
+    /// printf-style names that are skipped when looking for the last available source
+    /// of an unlimited virtual dataset (0 by default, i.e. the search stops at the first
+    /// missing source).
     pub fn virtual_printf_gap(&mut self, gap_size: usize) -> &mut Self {
         self.virtual_printf_gap = Some(gap_size);
         self
@@ -1,5 +1,6 @@
 //! Dataset creation properties.
 
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::ptr;
@@ -17,6 +18,8 @@ use hdf5_sys::h5p::{
     H5Pset_attr_phase_change, H5Pset_chunk, H5Pset_external, H5Pset_fill_time, H5Pset_fill_value,
     H5Pset_layout, H5Pset_obj_track_times,
 };
+#[cfg(feature = "1.10.5")]
+use hdf5_sys::h5p::{H5Pget_dset_no_attrs_hint, H5Pset_dset_no_attrs_hint};
 use hdf5_sys::h5t::H5Tget_class;
 use hdf5_sys::h5z::H5Z_filter_t;
 #[cfg(feature = "1.10.0")]
@@ -32,7 +35,7 @@ use hdf5_types::{OwnedDynValue, TypeDescriptor};
 use crate::dim::Dimension;
 use crate::globals::H5P_DATASET_CREATE;
 use crate::hl::datatype::Datatype;
-use crate::hl::filters::{validate_filters, Filter, SZip, ScaleOffset};
+use crate::hl::filters::{validate_filters, Filter, PipelineFilter, SZip, ScaleOffset};
 #[cfg(feature = "blosc")]
 use crate::hl::filters::{Blosc, BloscShuffle};
 pub use crate::hl::plist::common::{AttrCreationOrder, AttrPhaseChange};
@@ -80,6 +83,8 @@ impl Debug for DatasetCreate {
         formatter.field("obj_track_times", &self.obj_track_times());
         formatter.field("attr_phase_change", &self.attr_phase_change());
         formatter.field("attr_creation_order", &self.attr_creation_order());
+        #[cfg(feature = "1.10.5")]
+        formatter.field("dset_no_attrs_hint", &self.dset_no_attrs_hint());
         formatter.finish()
     }
 }
@@ -299,6 +304,7 @@ impl VirtualMapping {
 #[derive(Clone, Debug, Default)]
 pub struct DatasetCreateBuilder {
     filters: Vec<Filter>,
+    filter_flags: HashMap<H5Z_filter_t, bool>,
     #[allow(clippy::option_option)]
     alloc_time: Option<Option<AllocTime>>,
     fill_time: Option<FillTime>,
@@ -313,6 +319,8 @@ pub struct DatasetCreateBuilder {
     obj_track_times: Option<bool>,
     attr_phase_change: Option<AttrPhaseChange>,
     attr_creation_order: Option<AttrCreationOrder>,
+    #[cfg(feature = "1.10.5")]
+    dset_no_attrs_hint: Option<bool>,
 }
 
 impl DatasetCreateBuilder {
@@ -327,7 +335,7 @@ impl DatasetCreateBuilder {
     /// exposed in the property list API).
     pub fn from_plist(plist: &DatasetCreate) -> Result<Self> {
         let mut builder = Self::default();
-        builder.set_filters(&plist.get_filters()?);
+        builder.filter_pipeline(&plist.get_filter_pipeline()?);
         builder.alloc_time(Some(plist.get_alloc_time()?));
         builder.fill_time(plist.get_fill_time()?);
         if let Some(v) = plist.get_chunk()? {
@@ -360,11 +368,24 @@ impl DatasetCreateBuilder {
         let apc = plist.get_attr_phase_change()?;
         builder.attr_phase_change(apc.max_compact, apc.min_dense);
         builder.attr_creation_order(plist.get_attr_creation_order()?);
+        #[cfg(feature = "1.10.5")]
+        builder.dset_no_attrs_hint(plist.get_dset_no_attrs_hint()?);
         Ok(builder)
     }
 
     pub fn set_filters(&mut self, filters: &[Filter]) -> &mut Self {
         self.filters = filters.to_owned();
+        self.filter_flags.clear();
+        self
+    }
+
+    /// Sets the filter pipeline, replacing any previously added filters.
+    ///
+    /// Unlike `set_filters()`, each filter is explicitly marked as optional or mandatory;
+    /// see [`FilterPipelineBuilder`](crate::filters::FilterPipelineBuilder) for details.
+    pub fn filter_pipeline(&mut self, pipeline: &[PipelineFilter]) -> &mut Self {
+        self.filters = pipeline.iter().map(|f| f.filter.clone()).collect();
+        self.filter_flags = pipeline.iter().map(|f| (f.filter.id(), f.optional)).collect();
         self
     }
 
@@ -480,6 +501,7 @@ impl DatasetCreateBuilder {
 
     pub fn clear_filters(&mut self) -> &mut Self {
         self.filters.clear();
+        self.filter_flags.clear();
         self
     }
 
@@ -579,9 +601,20 @@ impl DatasetCreateBuilder {
         self
     }
 
+    #[cfg(feature = "1.10.5")]
+    /// Sets the hint to minimize the object header of the dataset, which saves space in the
+    /// file for datasets that don't have any attributes.
+    pub fn dset_no_attrs_hint(&mut self, minimize: bool) -> &mut Self {
+        self.dset_no_attrs_hint = Some(minimize);
+        self
+    }
+
     fn populate_plist(&self, id: hid_t) -> Result<()> {
         for filter in &self.filters {
             filter.apply_to_plist(id)?;
+            if let Some(&optional) = self.filter_flags.get(&filter.id()) {
+                filter.set_optional(id, optional)?;
+            }
         }
         if let Some(v) = self.alloc_time {
             let v = v.map_or(H5D_alloc_time_t::H5D_ALLOC_TIME_DEFAULT, Into::into);
@@ -634,6 +667,12 @@ impl DatasetCreateBuilder {
         if let Some(v) = self.attr_creation_order {
             h5try!(H5Pset_attr_creation_order(id, v.bits() as _));
         }
+        #[cfg(feature = "1.10.5")]
+        {
+            if let Some(v) = self.dset_no_attrs_hint {
+                h5try!(H5Pset_dset_no_attrs_hint(id, hbool_t::from(v)));
+            }
+        }
         Ok(())
     }
 
@@ -645,6 +684,11 @@ impl DatasetCreateBuilder {
         !self.filters.is_empty()
     }
 
+    #[cfg(feature = "1.10.0")]
+    pub(crate) fn has_virtual_map(&self) -> bool {
+        !self.virtual_map.is_empty()
+    }
+
     pub fn apply(&self, plist: &mut DatasetCreate) -> Result<()> {
         h5lock!(self.populate_plist(plist.id()))
     }
@@ -684,6 +728,16 @@ impl DatasetCreate {
         self.get_filters().unwrap_or_default()
     }
 
+    #[doc(hidden)]
+    pub fn get_filter_pipeline(&self) -> Result<Vec<PipelineFilter>> {
+        Filter::extract_pipeline_flags(self.id())
+    }
+
+    /// Returns the filters along with their flags, in the order they are applied.
+    pub fn filter_pipeline(&self) -> Vec<PipelineFilter> {
+        self.get_filter_pipeline().unwrap_or_default()
+    }
+
     pub fn has_filters(&self) -> bool {
         !self.filters().is_empty()
     }
@@ -896,4 +950,15 @@ impl DatasetCreate {
     pub fn attr_creation_order(&self) -> AttrCreationOrder {
         self.get_attr_creation_order().unwrap_or_default()
     }
+
+    #[cfg(feature = "1.10.5")]
+    #[doc(hidden)]
+    pub fn get_dset_no_attrs_hint(&self) -> Result<bool> {
+        h5get!(H5Pget_dset_no_attrs_hint(self.id()): hbool_t).map(|x| x > 0)
+    }
+
+    #[cfg(feature = "1.10.5")]
+    pub fn dset_no_attrs_hint(&self) -> bool {
+        self.get_dset_no_attrs_hint().unwrap_or(false)
+    }
 }
@@ -0,0 +1,369 @@
+//! Dataset transfer properties.
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::ptr;
+
+#[cfg(feature = "have-parallel")]
+use hdf5_sys::h5p::{
+    H5FD_mpio_collective_opt_t, H5FD_mpio_xfer_t, H5Pget, H5Pget_dxpl_mpio, H5Pset_dxpl_mpio,
+    H5Pset_dxpl_mpio_collective_opt,
+};
+use hdf5_sys::h5p::{
+    H5Pcreate, H5Pget_buffer, H5Pget_data_transform, H5Pget_edc_check, H5Pget_hyper_vector_size,
+    H5Pset_buffer, H5Pset_data_transform, H5Pset_edc_check, H5Pset_hyper_vector_size,
+};
+use hdf5_sys::h5z::{H5Z_DISABLE_EDC, H5Z_ENABLE_EDC};
+
+use crate::globals::H5P_DATASET_XFER;
+use crate::internal_prelude::*;
+
+/// Dataset transfer properties.
+#[repr(transparent)]
+pub struct DatasetTransfer(Handle);
+
+impl ObjectClass for DatasetTransfer {
+    const NAME: &'static str = "dataset transfer property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        let class = self.class()?;
+        if class != PropertyListClass::DataTransfer {
+            fail!("expected dataset transfer property list, got {:?}", class);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for DatasetTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("DataTransfer");
+        formatter.field("buffer_size", &self.buffer_size());
+        formatter.field("edc_check", &self.edc_check());
+        formatter.field("data_transform", &self.data_transform());
+        formatter.field("hyper_vector_size", &self.hyper_vector_size());
+        #[cfg(feature = "have-parallel")]
+        formatter.field("io_mode", &self.io_mode());
+        #[cfg(feature = "have-parallel")]
+        formatter.field("collective_opt", &self.collective_opt());
+        formatter.finish()
+    }
+}
+
+impl Deref for DatasetTransfer {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for DatasetTransfer {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for DatasetTransfer {}
+
+impl Clone for DatasetTransfer {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Parallel I/O transfer mode (requires the MPI-IO file driver).
+#[cfg(feature = "have-parallel")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoMode {
+    /// Each process performs its I/O independently.
+    Independent,
+    /// All processes participate in each I/O operation.
+    Collective,
+}
+
+#[cfg(feature = "have-parallel")]
+impl Default for IoMode {
+    fn default() -> Self {
+        Self::Independent
+    }
+}
+
+#[cfg(feature = "have-parallel")]
+impl From<H5FD_mpio_xfer_t> for IoMode {
+    fn from(mode: H5FD_mpio_xfer_t) -> Self {
+        match mode {
+            H5FD_mpio_xfer_t::H5FD_MPIO_COLLECTIVE => Self::Collective,
+            H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT => Self::Independent,
+        }
+    }
+}
+
+#[cfg(feature = "have-parallel")]
+impl From<IoMode> for H5FD_mpio_xfer_t {
+    fn from(mode: IoMode) -> Self {
+        match mode {
+            IoMode::Collective => Self::H5FD_MPIO_COLLECTIVE,
+            IoMode::Independent => Self::H5FD_MPIO_INDEPENDENT,
+        }
+    }
+}
+
+/// Low-level I/O used by collective operations (see [`IoMode::Collective`]).
+#[cfg(feature = "have-parallel")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollectiveOpt {
+    /// Use collective I/O calls.
+    CollectiveIo,
+    /// Keep the collective semantics, but use individual I/O calls for raw data.
+    IndividualIo,
+}
+
+#[cfg(feature = "have-parallel")]
+impl Default for CollectiveOpt {
+    fn default() -> Self {
+        Self::CollectiveIo
+    }
+}
+
+#[cfg(feature = "have-parallel")]
+impl From<CollectiveOpt> for H5FD_mpio_collective_opt_t {
+    fn from(opt: CollectiveOpt) -> Self {
+        match opt {
+            CollectiveOpt::CollectiveIo => Self::H5FD_MPIO_COLLECTIVE_IO,
+            CollectiveOpt::IndividualIo => Self::H5FD_MPIO_INDIVIDUAL_IO,
+        }
+    }
+}
+
+/// Builder used to create dataset transfer property list.
+#[derive(Clone, Debug, Default)]
+pub struct DatasetTransferBuilder {
+    buffer_size: Option<usize>,
+    edc_check: Option<bool>,
+    data_transform: Option<String>,
+    hyper_vector_size: Option<usize>,
+    #[cfg(feature = "have-parallel")]
+    io_mode: Option<IoMode>,
+    #[cfg(feature = "have-parallel")]
+    collective_opt: Option<CollectiveOpt>,
+}
+
+impl DatasetTransferBuilder {
+    /// Creates a new dataset transfer property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &DatasetTransfer) -> Result<Self> {
+        let mut builder = Self::default();
+        builder.buffer_size(plist.get_buffer_size()?);
+        builder.edc_check(plist.get_edc_check()?);
+        if let Some(expression) = plist.get_data_transform()? {
+            builder.data_transform(&expression);
+        }
+        builder.hyper_vector_size(plist.get_hyper_vector_size()?);
+        #[cfg(feature = "have-parallel")]
+        builder.io_mode(plist.get_io_mode()?);
+        #[cfg(feature = "have-parallel")]
+        builder.collective_opt(plist.get_collective_opt()?);
+        Ok(builder)
+    }
+
+    /// Sets the size of the buffers used for type conversion and background values
+    /// (1 MiB by default); the buffers are allocated by the library as needed.
+    pub fn buffer_size(&mut self, size: usize) -> &mut Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    /// Enables or disables checksum verification (error detection) while reading.
+    pub fn edc_check(&mut self, enable: bool) -> &mut Self {
+        self.edc_check = Some(enable);
+        self
+    }
+
+    /// Sets an arithmetic expression (e.g. `"x/100"`) which is applied to each element
+    /// as part of the transfer.
+    pub fn data_transform(&mut self, expression: &str) -> &mut Self {
+        self.data_transform = Some(expression.into());
+        self
+    }
+
+    /// Sets the number of I/O vectors (offset/length pairs) built up by the library when
+    /// transferring hyperslab selections (1024 by default); larger values may improve the
+    /// performance of reading or writing selections made of many small blocks.
+    pub fn hyper_vector_size(&mut self, size: usize) -> &mut Self {
+        self.hyper_vector_size = Some(size);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Sets the parallel I/O transfer mode (independent by default).
+    pub fn io_mode(&mut self, mode: IoMode) -> &mut Self {
+        self.io_mode = Some(mode);
+        self
+    }
+
+    #[cfg(feature = "have-parallel")]
+    /// Selects whether collective operations use collective or individual low-level I/O
+    /// calls for raw data; only has effect if the transfer mode is collective.
+    pub fn collective_opt(&mut self, opt: CollectiveOpt) -> &mut Self {
+        self.collective_opt = Some(opt);
+        self
+    }
+
+    pub(crate) fn is_default(&self) -> bool {
+        let is_default = self.buffer_size.is_none()
+            && self.edc_check.is_none()
+            && self.data_transform.is_none()
+            && self.hyper_vector_size.is_none();
+        #[cfg(feature = "have-parallel")]
+        let is_default = is_default && self.io_mode.is_none() && self.collective_opt.is_none();
+        is_default
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(size) = self.buffer_size {
+            h5try!(H5Pset_buffer(id, size as _, ptr::null_mut(), ptr::null_mut()));
+        }
+        if let Some(enable) = self.edc_check {
+            h5try!(H5Pset_edc_check(id, if enable { H5Z_ENABLE_EDC } else { H5Z_DISABLE_EDC }));
+        }
+        if let Some(ref expression) = self.data_transform {
+            let expression = to_cstring(expression.as_str())?;
+            h5try!(H5Pset_data_transform(id, expression.as_ptr()));
+        }
+        if let Some(size) = self.hyper_vector_size {
+            h5try!(H5Pset_hyper_vector_size(id, size as _));
+        }
+        #[cfg(feature = "have-parallel")]
+        {
+            if let Some(mode) = self.io_mode {
+                h5try!(H5Pset_dxpl_mpio(id, mode.into()));
+            }
+            if let Some(opt) = self.collective_opt {
+                h5try!(H5Pset_dxpl_mpio_collective_opt(id, opt.into()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, plist: &mut DatasetTransfer) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    pub fn finish(&self) -> Result<DatasetTransfer> {
+        h5lock!({
+            let mut plist = DatasetTransfer::try_new()?;
+            self.apply(&mut plist).map(|_| plist)
+        })
+    }
+}
+
+/// Dataset transfer property list.
+impl DatasetTransfer {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_DATASET_XFER)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast_unchecked() }
+    }
+
+    pub fn build() -> DatasetTransferBuilder {
+        DatasetTransferBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_buffer_size(&self) -> Result<usize> {
+        h5lock!({
+            let size = H5Pget_buffer(self.id(), ptr::null_mut(), ptr::null_mut());
+            ensure!(size > 0, "Failed to get the type conversion buffer size");
+            Ok(size as _)
+        })
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.get_buffer_size().unwrap_or(1024 * 1024)
+    }
+
+    #[doc(hidden)]
+    pub fn get_edc_check(&self) -> Result<bool> {
+        match h5lock!(H5Pget_edc_check(self.id())) {
+            H5Z_ENABLE_EDC => Ok(true),
+            H5Z_DISABLE_EDC => Ok(false),
+            edc => fail!("Invalid EDC check value: {:?}", edc),
+        }
+    }
+
+    pub fn edc_check(&self) -> bool {
+        self.get_edc_check().unwrap_or(true)
+    }
+
+    #[doc(hidden)]
+    pub fn get_data_transform(&self) -> Result<Option<String>> {
+        h5lock!({
+            let len = H5Pget_data_transform(self.id(), ptr::null_mut(), 0);
+            if len <= 0 {
+                // the library reports an error if no transform has been set
+                return Ok(None);
+            }
+            get_h5_str(|m, s| H5Pget_data_transform(self.id(), m, s)).map(Some)
+        })
+    }
+
+    pub fn data_transform(&self) -> Option<String> {
+        self.get_data_transform().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_hyper_vector_size(&self) -> Result<usize> {
+        h5get!(H5Pget_hyper_vector_size(self.id()): size_t).map(|x| x as _)
+    }
+
+    pub fn hyper_vector_size(&self) -> usize {
+        self.get_hyper_vector_size().unwrap_or(1024)
+    }
+
+    #[cfg(feature = "have-parallel")]
+    #[doc(hidden)]
+    pub fn get_io_mode(&self) -> Result<IoMode> {
+        let mut mode = H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT;
+        h5call!(H5Pget_dxpl_mpio(self.id(), &mut mode)).map(|_| mode.into())
+    }
+
+    #[cfg(feature = "have-parallel")]
+    pub fn io_mode(&self) -> IoMode {
+        self.get_io_mode().unwrap_or_default()
+    }
+
+    #[cfg(feature = "have-parallel")]
+    #[doc(hidden)]
+    pub fn get_collective_opt(&self) -> Result<CollectiveOpt> {
+        // there's no dedicated getter, so the library-defined property is read directly
+        let name = to_cstring("mpio_collective_opt")?;
+        let mut opt: c_int = H5FD_mpio_collective_opt_t::H5FD_MPIO_COLLECTIVE_IO as _;
+        h5call!(H5Pget(self.id(), name.as_ptr(), (&mut opt as *mut c_int).cast())).map(|_| {
+            if opt == H5FD_mpio_collective_opt_t::H5FD_MPIO_INDIVIDUAL_IO as c_int {
+                CollectiveOpt::IndividualIo
+            } else {
+                CollectiveOpt::CollectiveIo
+            }
+        })
+    }
+
+    #[cfg(feature = "have-parallel")]
+    pub fn collective_opt(&self) -> CollectiveOpt {
+        self.get_collective_opt().unwrap_or_default()
+    }
+}
@@ -65,6 +65,8 @@ use hdf5_sys::h5p::{
     H5Pget_evict_on_close, H5Pget_mdc_image_config, H5Pget_page_buffer_size, H5Pset_evict_on_close,
     H5Pset_mdc_image_config, H5Pset_page_buffer_size,
 };
+#[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+use hdf5_sys::h5p::{H5Pget_file_locking, H5Pset_file_locking};
 #[cfg(feature = "1.10.2")]
 use hdf5_sys::h5p::{H5Pget_libver_bounds, H5Pset_libver_bounds};
 #[cfg(feature = "1.10.0")]
@@ -124,6 +126,8 @@ impl Debug for FileAccess {
         formatter.field("page_buffer_size", &self.page_buffer_size());
         #[cfg(feature = "1.10.1")]
         formatter.field("evict_on_close", &self.evict_on_close());
+        #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+        formatter.field("file_locking", &self.file_locking());
         #[cfg(feature = "1.10.1")]
         formatter.field("mdc_image_config", &self.mdc_image_config());
         formatter.field("sieve_buf_size", &self.sieve_buf_size());
@@ -551,6 +555,18 @@ pub struct PageBufferSize {
     pub min_raw_perc: u32,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileLocking {
+    pub use_file_locking: bool,
+    pub ignore_when_disabled: bool,
+}
+
+impl Default for FileLocking {
+    fn default() -> Self {
+        Self { use_file_locking: true, ignore_when_disabled: false }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CacheIncreaseMode {
     Off,
@@ -958,6 +974,8 @@ pub struct FileAccessBuilder {
     sieve_buf_size: Option<usize>,
     #[cfg(feature = "1.10.1")]
     evict_on_close: Option<bool>,
+    #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+    file_locking: Option<FileLocking>,
     #[cfg(feature = "1.10.0")]
     metadata_read_attempts: Option<u32>,
     mdc_config: Option<MetadataCacheConfig>,
@@ -1010,6 +1028,11 @@ impl FileAccessBuilder {
             builder.evict_on_close(plist.get_evict_on_close()?);
             builder.mdc_image_config(plist.get_mdc_image_config()?.generate_image);
         }
+        #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+        {
+            let v = plist.get_file_locking()?;
+            builder.file_locking(v.use_file_locking, v.ignore_when_disabled);
+        }
         builder.sieve_buf_size(plist.get_sieve_buf_size()?);
         #[cfg(feature = "1.10.0")]
         {
@@ -1082,6 +1105,19 @@ impl FileAccessBuilder {
         self
     }
 
+    /// Sets whether file locking is used when opening files.
+    ///
+    /// Disabling locking may be required on file systems that do not support it (e.g.
+    /// some NFS setups); `ignore_when_disabled` makes HDF5 ignore locking failures on
+    /// file systems where locking has been disabled instead of returning an error.
+    #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+    pub fn file_locking(
+        &mut self, use_file_locking: bool, ignore_when_disabled: bool,
+    ) -> &mut Self {
+        self.file_locking = Some(FileLocking { use_file_locking, ignore_when_disabled });
+        self
+    }
+
     #[cfg(feature = "1.10.0")]
     pub fn metadata_read_attempts(&mut self, attempts: u32) -> &mut Self {
         self.metadata_read_attempts = Some(attempts);
@@ -1173,6 +1209,14 @@ impl FileAccessBuilder {
         self.driver(&FileDriver::Stdio)
     }
 
+    /// Selects the `log` driver which traces the file I/O (e.g. the locations and sizes of
+    /// all reads and writes, as selected by `flags`) into `logfile` (or to `stderr` if not
+    /// specified); the summary statistics (e.g. `LogFlags::NUM_IO`) are written out when
+    /// the file is closed.
+    ///
+    /// `buf_size` is the size of the buffers used to track per-byte access counts and the
+    /// kind of data stored at each byte (`LogFlags::FILE_IO` and `LogFlags::FLAVOR`); it
+    /// should be at least as large as the file.
     pub fn log_options(
         &mut self, logfile: Option<&str>, flags: LogFlags, buf_size: usize,
     ) -> &mut Self {
@@ -1201,6 +1245,12 @@ impl FileAccessBuilder {
     }
 
     #[cfg(feature = "1.8.13")]
+    /// Enables write tracking for the file-backed core driver with the given page size
+    /// (or disables it if `page_size` is zero).
+    ///
+    /// With write tracking, only the pages modified since the last flush are written to
+    /// the backing store instead of the whole in-memory image; larger pages result in
+    /// fewer, but bigger writes. Must be used together with one of the `core*()` methods.
     pub fn write_tracking(&mut self, page_size: usize) -> &mut Self {
         self.write_tracking = Some(page_size);
         self
@@ -1452,6 +1502,16 @@ impl FileAccessBuilder {
                 h5try!(H5Pset_mdc_image_config(id, &v.into() as *const _));
             }
         }
+        #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+        {
+            if let Some(v) = self.file_locking {
+                h5try!(H5Pset_file_locking(
+                    id,
+                    hbool_t::from(v.use_file_locking),
+                    hbool_t::from(v.ignore_when_disabled),
+                ));
+            }
+        }
         if let Some(v) = self.sieve_buf_size {
             h5try!(H5Pset_sieve_buf_size(id, v as _));
         }
@@ -1727,6 +1787,22 @@ impl FileAccess {
         self.get_evict_on_close().unwrap_or(false)
     }
 
+    #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+    #[doc(hidden)]
+    pub fn get_file_locking(&self) -> Result<FileLocking> {
+        h5get!(H5Pget_file_locking(self.id()): hbool_t, hbool_t).map(
+            |(use_file_locking, ignore_when_disabled)| FileLocking {
+                use_file_locking: use_file_locking > 0,
+                ignore_when_disabled: ignore_when_disabled > 0,
+            },
+        )
+    }
+
+    #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+    pub fn file_locking(&self) -> FileLocking {
+        self.get_file_locking().unwrap_or_else(|_| FileLocking::default())
+    }
+
     #[cfg(feature = "1.10.0")]
     #[doc(hidden)]
     pub fn get_metadata_read_attempts(&self) -> Result<u32> {
@@ -0,0 +1,281 @@
+//! Group creation properties.
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+use bitflags::bitflags;
+
+use hdf5_sys::h5p::{
+    H5Pcreate, H5Pget_est_link_info, H5Pget_link_creation_order, H5Pget_link_phase_change,
+    H5Pget_obj_track_times, H5Pset_est_link_info, H5Pset_link_creation_order,
+    H5Pset_link_phase_change, H5Pset_obj_track_times, H5P_CRT_ORDER_INDEXED, H5P_CRT_ORDER_TRACKED,
+};
+
+use crate::globals::H5P_GROUP_CREATE;
+use crate::internal_prelude::*;
+
+/// Group creation properties.
+#[repr(transparent)]
+pub struct GroupCreate(Handle);
+
+impl ObjectClass for GroupCreate {
+    const NAME: &'static str = "group create property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        let class = self.class()?;
+        if class != PropertyListClass::GroupCreate {
+            fail!("expected group create property list, got {:?}", class);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for GroupCreate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("GroupCreate");
+        formatter.field("est_link_info", &self.est_link_info());
+        formatter.field("link_phase_change", &self.link_phase_change());
+        formatter.field("link_creation_order", &self.link_creation_order());
+        formatter.field("obj_track_times", &self.obj_track_times());
+        formatter.finish()
+    }
+}
+
+impl Deref for GroupCreate {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for GroupCreate {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for GroupCreate {}
+
+impl Clone for GroupCreate {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Estimated number of links in a group and the average length of their names.
+///
+/// These estimates are used to pre-size the object header of a group using compact
+/// link storage, so that it doesn't have to be extended as the links are added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EstLinkInfo {
+    /// Estimated number of links (default: 4).
+    pub num_entries: u32,
+    /// Estimated average length of link names (default: 8).
+    pub name_len: u32,
+}
+
+impl Default for EstLinkInfo {
+    fn default() -> Self {
+        Self { num_entries: 4, name_len: 8 }
+    }
+}
+
+/// Link storage phase change thresholds.
+///
+/// These thresholds determine the point at which link storage of a group changes from
+/// compact storage (i.e., storage in the object header) to dense storage (i.e., storage
+/// in a heap and indexed with a B-tree).
+///
+/// When the number of links exceeds `max_compact`, link storage switches to dense storage.
+/// If the number of links subsequently falls below `min_dense`, the links are returned to
+/// compact storage.
+///
+/// Note that these thresholds only apply to groups in the "new" format, i.e. when using
+/// the 1.8 (or later) file format (see `FileAccessBuilder::libver_bounds()`); otherwise,
+/// links are always stored in a symbol table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkPhaseChange {
+    /// Maximum number of links to be stored in compact storage (default: 8).
+    pub max_compact: u32,
+    /// Minimum number of links to be stored in dense storage (default: 6).
+    pub min_dense: u32,
+}
+
+impl Default for LinkPhaseChange {
+    fn default() -> Self {
+        Self { max_compact: 8, min_dense: 6 }
+    }
+}
+
+bitflags! {
+    /// Flags for tracking and indexing link creation order of a group.
+    ///
+    /// Default behavior is that link creation order is neither tracked nor indexed, so
+    /// that links can only be iterated in the order of their names.
+    ///
+    /// Note that if a creation order index is to be built, it must be specified in
+    /// the group creation property list; it cannot be added to an existing group.
+    #[derive(Default)]
+    pub struct LinkCreationOrder: u32 {
+        /// Link creation order is tracked but not necessarily indexed.
+        const TRACKED = H5P_CRT_ORDER_TRACKED as _;
+        /// Link creation order is indexed (requires to be tracked).
+        const INDEXED = H5P_CRT_ORDER_INDEXED as _;
+    }
+}
+
+/// Builder used to create group creation property list.
+#[derive(Clone, Debug, Default)]
+pub struct GroupCreateBuilder {
+    est_link_info: Option<EstLinkInfo>,
+    link_phase_change: Option<LinkPhaseChange>,
+    link_creation_order: Option<LinkCreationOrder>,
+    obj_track_times: Option<bool>,
+}
+
+impl GroupCreateBuilder {
+    /// Creates a new group creation property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &GroupCreate) -> Result<Self> {
+        let mut builder = Self::default();
+        let v = plist.get_est_link_info()?;
+        builder.est_link_info(v.num_entries, v.name_len);
+        let v = plist.get_link_phase_change()?;
+        builder.link_phase_change(v.max_compact, v.min_dense);
+        builder.link_creation_order(plist.get_link_creation_order()?);
+        builder.obj_track_times(plist.get_obj_track_times()?);
+        Ok(builder)
+    }
+
+    /// Sets the estimated number of links in the group and the average length of
+    /// their names.
+    ///
+    /// For further details, see [`EstLinkInfo`](struct.EstLinkInfo.html).
+    pub fn est_link_info(&mut self, num_entries: u32, name_len: u32) -> &mut Self {
+        self.est_link_info = Some(EstLinkInfo { num_entries, name_len });
+        self
+    }
+
+    /// Sets link storage phase change thresholds.
+    ///
+    /// For further details, see [`LinkPhaseChange`](struct.LinkPhaseChange.html).
+    pub fn link_phase_change(&mut self, max_compact: u32, min_dense: u32) -> &mut Self {
+        self.link_phase_change = Some(LinkPhaseChange { max_compact, min_dense });
+        self
+    }
+
+    /// Sets flags for tracking and indexing link creation order.
+    ///
+    /// For further details, see [`LinkCreationOrder`](struct.LinkCreationOrder.html).
+    pub fn link_creation_order(&mut self, link_creation_order: LinkCreationOrder) -> &mut Self {
+        self.link_creation_order = Some(link_creation_order);
+        self
+    }
+
+    /// Sets whether the access, modification, change and birth times of the group are
+    /// recorded (enabled by default); disabling this makes files with identical contents
+    /// byte-identical.
+    pub fn obj_track_times(&mut self, track_times: bool) -> &mut Self {
+        self.obj_track_times = Some(track_times);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(v) = self.est_link_info {
+            h5try!(H5Pset_est_link_info(id, v.num_entries as _, v.name_len as _));
+        }
+        if let Some(v) = self.link_phase_change {
+            h5try!(H5Pset_link_phase_change(id, v.max_compact as _, v.min_dense as _));
+        }
+        if let Some(v) = self.link_creation_order {
+            h5try!(H5Pset_link_creation_order(id, v.bits() as _));
+        }
+        if let Some(v) = self.obj_track_times {
+            h5try!(H5Pset_obj_track_times(id, hbool_t::from(v)));
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, plist: &mut GroupCreate) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    pub fn finish(&self) -> Result<GroupCreate> {
+        h5lock!({
+            let mut plist = GroupCreate::try_new()?;
+            self.apply(&mut plist).map(|_| plist)
+        })
+    }
+}
+
+/// Group creation property list.
+impl GroupCreate {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_GROUP_CREATE)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast_unchecked() }
+    }
+
+    pub fn build() -> GroupCreateBuilder {
+        GroupCreateBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_est_link_info(&self) -> Result<EstLinkInfo> {
+        h5get!(H5Pget_est_link_info(self.id()): c_uint, c_uint)
+            .map(|(n, len)| EstLinkInfo { num_entries: n as _, name_len: len as _ })
+    }
+
+    /// Returns the estimated number of links and the average length of their names.
+    pub fn est_link_info(&self) -> EstLinkInfo {
+        self.get_est_link_info().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_link_phase_change(&self) -> Result<LinkPhaseChange> {
+        h5get!(H5Pget_link_phase_change(self.id()): c_uint, c_uint)
+            .map(|(mc, md)| LinkPhaseChange { max_compact: mc as _, min_dense: md as _ })
+    }
+
+    /// Returns link storage phase change thresholds.
+    pub fn link_phase_change(&self) -> LinkPhaseChange {
+        self.get_link_phase_change().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_link_creation_order(&self) -> Result<LinkCreationOrder> {
+        h5get!(H5Pget_link_creation_order(self.id()): c_uint)
+            .map(LinkCreationOrder::from_bits_truncate)
+    }
+
+    /// Returns flags for tracking and indexing link creation order.
+    pub fn link_creation_order(&self) -> LinkCreationOrder {
+        self.get_link_creation_order().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_obj_track_times(&self) -> Result<bool> {
+        h5get!(H5Pget_obj_track_times(self.id()): hbool_t).map(|x| x > 0)
+    }
+
+    /// Returns whether the times associated with the group are recorded.
+    pub fn obj_track_times(&self) -> bool {
+        self.get_obj_track_times().unwrap_or(true)
+    }
+}
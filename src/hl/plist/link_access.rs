@@ -0,0 +1,134 @@
+//! Link access properties.
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+use hdf5_sys::h5p::{H5Pcreate, H5Pget_nlinks, H5Pset_nlinks};
+
+use crate::globals::H5P_LINK_ACCESS;
+use crate::internal_prelude::*;
+
+/// Link access properties.
+#[repr(transparent)]
+pub struct LinkAccess(Handle);
+
+impl ObjectClass for LinkAccess {
+    const NAME: &'static str = "link access property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        let class = self.class()?;
+        if class != PropertyListClass::LinkAccess {
+            fail!("expected link access property list, got {:?}", class);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for LinkAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("LinkAccess");
+        formatter.field("nlinks", &self.nlinks());
+        formatter.finish()
+    }
+}
+
+impl Deref for LinkAccess {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for LinkAccess {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for LinkAccess {}
+
+impl Clone for LinkAccess {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Builder used to create link access property list.
+#[derive(Clone, Debug, Default)]
+pub struct LinkAccessBuilder {
+    nlinks: Option<usize>,
+}
+
+impl LinkAccessBuilder {
+    /// Creates a new link access property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &LinkAccess) -> Result<Self> {
+        let mut builder = Self::default();
+        builder.nlinks(plist.get_nlinks()?);
+        Ok(builder)
+    }
+
+    /// Sets the maximum number of soft or user-defined (e.g. external) link traversals
+    /// while resolving a path (16 by default); exceeding it is treated as an error,
+    /// which prevents infinite loops when resolving cyclic links.
+    pub fn nlinks(&mut self, nlinks: usize) -> &mut Self {
+        self.nlinks = Some(nlinks);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(nlinks) = self.nlinks {
+            h5try!(H5Pset_nlinks(id, nlinks as _));
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, plist: &mut LinkAccess) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    pub fn finish(&self) -> Result<LinkAccess> {
+        h5lock!({
+            let mut plist = LinkAccess::try_new()?;
+            self.apply(&mut plist).map(|_| plist)
+        })
+    }
+}
+
+/// Link access property list.
+impl LinkAccess {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_LINK_ACCESS)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast_unchecked() }
+    }
+
+    pub fn build() -> LinkAccessBuilder {
+        LinkAccessBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_nlinks(&self) -> Result<usize> {
+        h5get!(H5Pget_nlinks(self.id()): size_t).map(|x| x as _)
+    }
+
+    pub fn nlinks(&self) -> usize {
+        self.get_nlinks().unwrap_or(16)
+    }
+}
@@ -8,9 +8,9 @@ use std::slice;
 use ndarray::{self, s, Array1, Array2, ArrayView1, ArrayView2};
 
 use hdf5_sys::h5s::{
-    H5S_sel_type, H5Sget_select_elem_npoints, H5Sget_select_elem_pointlist, H5Sget_select_type,
-    H5Sget_simple_extent_ndims, H5Sselect_all, H5Sselect_elements, H5Sselect_hyperslab,
-    H5Sselect_none, H5S_SELECT_SET, H5S_UNLIMITED,
+    H5S_sel_type, H5S_seloper_t, H5Sget_select_elem_npoints, H5Sget_select_elem_pointlist,
+    H5Sget_select_type, H5Sget_simple_extent_ndims, H5Sselect_all, H5Sselect_elements,
+    H5Sselect_hyperslab, H5Sselect_none, H5S_SELECT_OR, H5S_SELECT_SET, H5S_UNLIMITED,
 };
 #[cfg(feature = "1.10.0")]
 use hdf5_sys::h5s::{H5Sget_regular_hyperslab, H5Sis_regular_hyperslab};
@@ -76,7 +76,9 @@ unsafe fn get_regular_hyperslab(space_id: hid_t) -> Result<Option<RawHyperslab>>
     Ok(None)
 }
 
-unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result<()> {
+unsafe fn set_regular_hyperslab(
+    space_id: hid_t, hyper: &RawHyperslab, op: H5S_seloper_t,
+) -> Result<()> {
     let (mut start, mut stride, mut count, mut block) = (vec![], vec![], vec![], vec![]);
     for slice_info in hyper.iter() {
         start.push(slice_info.start as _);
@@ -86,7 +88,7 @@ unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result
     }
     h5check(H5Sselect_hyperslab(
         space_id,
-        H5S_SELECT_SET,
+        op,
         start.as_ptr(),
         stride.as_ptr(),
         count.as_ptr(),
@@ -95,6 +97,16 @@ unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result
     Ok(())
 }
 
+/// Selects the union of the given regular hyperslabs (nothing if there are none).
+pub(crate) unsafe fn set_hyperslab_union(space_id: hid_t, hypers: &[RawHyperslab]) -> Result<()> {
+    h5check(H5Sselect_none(space_id))?;
+    for (i, hyper) in hypers.iter().enumerate() {
+        let op = if i == 0 { H5S_SELECT_SET } else { H5S_SELECT_OR };
+        set_regular_hyperslab(space_id, hyper, op)?;
+    }
+    Ok(())
+}
+
 fn check_coords(coords: &Array2<Ix>, shape: &[Ix]) -> Result<()> {
     if coords.shape() == [0, 0] {
         return Ok(());
@@ -201,7 +213,9 @@ impl RawSelection {
                 h5check(H5Sselect_all(space_id))?;
             }
             Self::Points(ref coords) => set_points_selection(space_id, coords.view())?,
-            Self::RegularHyperslab(ref hyper) => set_regular_hyperslab(space_id, hyper)?,
+            Self::RegularHyperslab(ref hyper) => {
+                set_regular_hyperslab(space_id, hyper, H5S_SELECT_SET)?
+            }
             Self::ComplexHyperslab => fail!("Complex hyperslabs are not supported"),
         };
         Ok(())
@@ -217,6 +231,44 @@ impl RawSelection {
             sel_type => fail!("Invalid selection type: {:?}", sel_type as c_int),
         })
     }
+
+    /// Appends the coordinates of all selected points to `coords` (in the order in which
+    /// they would be transferred) and returns the number of points.
+    pub(crate) fn append_points(&self, shape: &[Ix], coords: &mut Vec<Ix>) -> Result<usize> {
+        let axes: Vec<Vec<Ix>> = match self {
+            Self::None => return Ok(0),
+            Self::All => shape.iter().map(|&dim| (0..dim).collect()).collect(),
+            Self::Points(ref points) => {
+                coords.extend(points.iter());
+                return Ok(points.nrows());
+            }
+            Self::RegularHyperslab(ref hyper) => hyper
+                .iter()
+                .map(|s| {
+                    let count = s.count.ok_or_else(|| {
+                        Error::from("Unable to get the points of unlimited hyperslab")
+                    })?;
+                    Ok((0..count)
+                        .flat_map(|i| (0..s.block).map(move |j| s.start + i * s.step + j))
+                        .collect())
+                })
+                .collect::<Result<_>>()?,
+            Self::ComplexHyperslab => fail!("Complex hyperslabs are not supported"),
+        };
+        let n_points = axes.iter().map(Vec::len).product();
+        let mut index = vec![0; axes.len()];
+        for _ in 0..n_points {
+            coords.extend(index.iter().zip(&axes).map(|(&i, axis)| axis[i]));
+            for (i, axis) in index.iter_mut().zip(&axes).rev() {
+                *i += 1;
+                if *i < axis.len() {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        Ok(n_points)
+    }
 }
 
 /// A selector of a one-dimensional array
@@ -897,6 +949,34 @@ impl Selection {
     pub fn is_hyperslab(&self) -> bool {
         matches!(self, Self::Hyperslab(_))
     }
+
+    /// Creates a hyperslab selection from a range along each axis.
+    pub fn slab<R: AsRef<[Range<Ix>]>>(ranges: R) -> Self {
+        let dims = ranges.as_ref().iter().cloned().map(SliceOrIndex::from).collect::<Vec<_>>();
+        Hyperslab::from(dims).into()
+    }
+
+    /// Creates a hyperslab selection of an array with the given shape which excludes
+    /// `margin[i]` elements on both sides of each axis `i`, e.g. the ghost cells.
+    pub fn interior<S, M>(shape: S, margin: M) -> Result<Self>
+    where
+        S: AsRef<[Ix]>,
+        M: AsRef<[Ix]>,
+    {
+        let (shape, margin) = (shape.as_ref(), margin.as_ref());
+        ensure!(
+            margin.len() == shape.len(),
+            "Margin ndim ({}) != shape ndim ({})",
+            margin.len(),
+            shape.len()
+        );
+        let mut ranges = Vec::with_capacity(shape.len());
+        for (axis, (&dim, &m)) in shape.iter().zip(margin).enumerate() {
+            ensure!(2 * m <= dim, "Margin {} is too large for axis {} with size {}", m, axis, dim);
+            ranges.push(m..dim - m);
+        }
+        Ok(Self::slab(ranges))
+    }
 }
 
 impl Display for Selection {
@@ -1614,6 +1694,23 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_selection_slab_interior() -> Result<()> {
+        assert_eq!(Selection::slab(&[1..3, 0..4]), Selection::new((1..3, 0..4)));
+        assert_eq!(Selection::interior(&[10, 20], &[2, 3])?, Selection::new((2..8, 3..17)));
+        assert_eq!(Selection::interior(&[4], &[2])?, Selection::new(2..2));
+        assert_err!(Selection::interior(&[4, 5], &[1]), "Margin ndim (1) != shape ndim (2)");
+        assert_err!(Selection::interior(&[4, 5], &[1, 3]), "Margin 3 is too large for axis 1");
+
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((10, 12), |(i, j)| (i * 12 + j) as i32);
+            let ds = file.new_dataset_builder().with_data(&arr).create("x").unwrap();
+            let sel = Selection::interior(ds.shape(), &[2, 2]).unwrap();
+            assert_eq!(ds.read_slice_2d::<i32, _>(sel).unwrap(), arr.slice(s![2..8, 2..10]));
+        });
+        Ok(())
+    }
+
     #[test]
     fn use_selection_on_dataset() {
         with_tmp_file(|file| {
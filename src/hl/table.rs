@@ -0,0 +1,129 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+use crate::internal_prelude::*;
+
+/// An append-only table of records stored in a resizable 1-D dataset
+/// (similar in spirit to the HDF5 packet table API).
+///
+/// Records of type `T` (typically a `#[derive(H5Type)]` struct) are appended in batches
+/// to the end of the table and can be read back by row ranges.
+pub struct Table<T> {
+    ds: Dataset,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Debug for Table<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Table").field("dataset", &self.ds).finish()
+    }
+}
+
+impl<T> Clone for Table<T> {
+    fn clone(&self) -> Self {
+        Self { ds: self.ds.clone(), _marker: PhantomData }
+    }
+}
+
+impl<T: H5Type> Table<T> {
+    /// Creates a new empty table in the given group.
+    pub fn create(group: &Group, name: &str) -> Result<Self> {
+        Self::from_dataset(group.new_dataset::<T>().shape(0..).create(name)?)
+    }
+
+    /// Opens an existing table in the given group.
+    pub fn open(group: &Group, name: &str) -> Result<Self> {
+        Self::from_dataset(group.dataset(name)?)
+    }
+
+    /// Wraps an existing dataset, which must be 1-dimensional and resizable.
+    pub fn from_dataset(ds: Dataset) -> Result<Self> {
+        ensure!(ds.ndim() == 1, "Tables must be 1-dimensional, got ndim = {}", ds.ndim());
+        ensure!(ds.is_resizable(), "Tables must be resizable");
+        Ok(Self { ds, _marker: PhantomData })
+    }
+
+    /// Returns the underlying dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+
+    /// Returns the number of records in the table.
+    pub fn len(&self) -> usize {
+        self.ds.size()
+    }
+
+    /// Returns `true` if the table contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the records to the end of the table.
+    pub fn append(&self, records: &[T]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let len = self.len();
+        self.ds.resize(len + records.len())?;
+        self.ds.write_slice(records, len..len + records.len())
+    }
+
+    /// Reads `n` records starting at the row `start`.
+    pub fn read_range(&self, start: usize, n: usize) -> Result<Vec<T>> {
+        let len = self.len();
+        ensure!(
+            start.checked_add(n).map_or(false, |end| end <= len),
+            "Range {}..{} is out of bounds for a table of length {}",
+            start,
+            start.saturating_add(n),
+            len
+        );
+        Ok(self.ds.read_slice_1d(start..start + n)?.into_raw_vec())
+    }
+
+    /// Reads all records in the table.
+    pub fn read_all(&self) -> Result<Vec<T>> {
+        self.ds.read_raw()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::internal_prelude::*;
+
+    use super::Table;
+
+    type Record = (u32, f64, bool);
+
+    fn records(range: std::ops::Range<u32>) -> Vec<Record> {
+        range.map(|id| (id, f64::from(id) / 2., id % 2 == 0)).collect()
+    }
+
+    #[test]
+    pub fn test_table() {
+        with_tmp_file(|file| {
+            let table = Table::<Record>::create(&file, "log").unwrap();
+            assert!(table.is_empty());
+            assert!(table.read_all().unwrap().is_empty());
+
+            for batch in &[0..3, 3..3, 3..10, 10..25] {
+                table.append(&records(batch.clone())).unwrap();
+                assert_eq!(table.len(), batch.end as usize);
+            }
+            assert_eq!(table.read_all().unwrap(), records(0..25));
+            assert_eq!(table.read_range(0, 1).unwrap(), records(0..1));
+            assert_eq!(table.read_range(2, 9).unwrap(), records(2..11));
+            assert!(table.read_range(25, 0).unwrap().is_empty());
+            assert_err!(table.read_range(20, 6), "out of bounds");
+
+            let table = Table::<Record>::open(&file, "log").unwrap();
+            table.append(&records(25..30)).unwrap();
+            assert_eq!(table.read_range(24, 6).unwrap(), records(24..30));
+
+            file.new_dataset::<Record>().shape(4).create("fixed").unwrap();
+            assert_err!(Table::<Record>::open(&file, "fixed"), "must be resizable");
+            file.new_dataset::<Record>().shape((0.., 2)).create("2d").unwrap();
+            assert_err!(Table::<Record>::open(&file, "2d"), "must be 1-dimensional");
+        })
+    }
+}
@@ -53,15 +53,18 @@ mod export {
     pub use crate::{
         class::from_id,
         dim::{Dimension, Ix},
-        error::{silence_errors, Error, ErrorFrame, ErrorStack, ExpandedErrorStack, Result},
+        error::{
+            silence_errors, Error, ErrorFrame, ErrorStack, ExpandedErrorStack, OpenError, Result,
+        },
         hl::extents::{Extent, Extents, SimpleExtents},
         hl::selection::{Hyperslab, Selection, SliceOrIndex},
         hl::{
-            Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
-            AttributeBuilderEmptyShape, ByteReader, Container, Conversion, Dataset, DatasetBuilder,
-            DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape, Dataspace, Datatype,
-            File, FileBuilder, Group, LinkInfo, LinkType, Location, LocationInfo, LocationToken,
-            LocationType, Object, PropertyList, Reader, Writer,
+            AnyArray, Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
+            AttributeBuilderEmptyShape, ByteReader, ByteWriter, Container, Conversion, Dataset,
+            DatasetBuilder, DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape,
+            Dataspace, Datatype, File, FileBuilder, Group, GroupBuilder, IterAction, LazyDataset,
+            LinkInfo, LinkTarget, LinkType, Location, LocationInfo, LocationToken, LocationType,
+            Object, PropertyList, Reader, Table, Writer,
         },
     };
 
@@ -78,17 +81,19 @@ mod export {
     pub mod dataset {
         #[cfg(feature = "1.10.5")]
         pub use crate::hl::dataset::ChunkInfo;
+        #[cfg(feature = "mmap")]
+        pub use crate::hl::dataset::MmapArray;
         pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder};
         pub use crate::hl::plist::dataset_access::*;
         pub use crate::hl::plist::dataset_create::*;
     }
 
     pub mod datatype {
-        pub use crate::hl::datatype::{ByteOrder, Conversion, Datatype};
+        pub use crate::hl::datatype::{ByteOrder, Conversion, Datatype, TypeClass};
     }
 
     pub mod file {
-        pub use crate::hl::file::{File, FileBuilder, OpenMode};
+        pub use crate::hl::file::{File, FileBuilder, FileIntent, ObjectTypes, OpenMode};
         pub use crate::hl::plist::file_access::*;
         pub use crate::hl::plist::file_create::*;
     }
@@ -96,8 +101,11 @@ mod export {
     pub mod plist {
         pub use crate::hl::plist::dataset_access::{DatasetAccess, DatasetAccessBuilder};
         pub use crate::hl::plist::dataset_create::{DatasetCreate, DatasetCreateBuilder};
+        pub use crate::hl::plist::dataset_transfer::{DatasetTransfer, DatasetTransferBuilder};
         pub use crate::hl::plist::file_access::{FileAccess, FileAccessBuilder};
         pub use crate::hl::plist::file_create::{FileCreate, FileCreateBuilder};
+        pub use crate::hl::plist::group_create::{GroupCreate, GroupCreateBuilder};
+        pub use crate::hl::plist::link_access::{LinkAccess, LinkAccessBuilder};
         pub use crate::hl::plist::link_create::{LinkCreate, LinkCreateBuilder};
         pub use crate::hl::plist::{PropertyList, PropertyListClass};
 
@@ -107,12 +115,21 @@ mod export {
         pub mod dataset_create {
             pub use crate::hl::plist::dataset_create::*;
         }
+        pub mod dataset_transfer {
+            pub use crate::hl::plist::dataset_transfer::*;
+        }
         pub mod file_access {
             pub use crate::hl::plist::file_access::*;
         }
         pub mod file_create {
             pub use crate::hl::plist::file_create::*;
         }
+        pub mod group_create {
+            pub use crate::hl::plist::group_create::*;
+        }
+        pub mod link_access {
+            pub use crate::hl::plist::link_access::*;
+        }
         pub mod link_create {
             pub use crate::hl::plist::link_create::*;
         }
@@ -198,12 +215,144 @@ pub fn is_library_threadsafe() -> bool {
     }
 }
 
+/// Returns true if the HDF5 library was built with parallel (MPI) support.
+///
+/// The MPI-IO file driver (`FileAccessBuilder::mpio()`) additionally requires the `mpio`
+/// feature to be enabled; parallel transfers can then be configured via
+/// `DatasetTransferBuilder::io_mode()` and `collective_opt()`.
+pub fn is_library_parallel() -> bool {
+    cfg!(feature = "have-parallel")
+}
+
+/// Frees all unused memory held by the internal free lists of the HDF5 library.
+///
+/// The library keeps the memory of released internal objects (such as datatype and
+/// dataspace descriptions, I/O buffers and metadata blocks) on free lists for reuse
+/// instead of returning it to the system allocator. Calling this function releases all
+/// of it; the library also does this automatically when it is shut down. Note that this
+/// doesn't affect the metadata or chunk caches of open files, which are freed when the
+/// files are closed.
+pub fn garbage_collect() -> Result<()> {
+    use hdf5_sys::h5::H5garbage_collect;
+    h5call!(H5garbage_collect()).and(Ok(()))
+}
+
+/// Size limits of the internal free lists of the HDF5 library, in bytes.
+///
+/// There are three kinds of free lists: ones for fixed-size "regular" objects, ones for
+/// arrays of objects, and ones for variable-size blocks. Each kind has a limit on the
+/// total size of all lists (`*_global`) and on the size of each list (`*_list`); a limit
+/// of `None` means no limit. When a limit is reached, the memory is returned to the
+/// system allocator instead of being kept for reuse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FreeListLimits {
+    pub regular_global: Option<usize>,
+    pub regular_list: Option<usize>,
+    pub array_global: Option<usize>,
+    pub array_list: Option<usize>,
+    pub block_global: Option<usize>,
+    pub block_list: Option<usize>,
+}
+
+impl Default for FreeListLimits {
+    /// Returns the default limits used by the HDF5 library.
+    fn default() -> Self {
+        const MB: usize = 1024 * 1024;
+        Self {
+            regular_global: Some(MB),
+            regular_list: Some(MB / 4),
+            array_global: Some(4 * MB),
+            array_list: Some(MB / 4),
+            block_global: Some(16 * MB),
+            block_list: Some(MB),
+        }
+    }
+}
+
+/// Sets the size limits of the internal free lists of the HDF5 library.
+///
+/// For example, setting all limits to zero makes the library return the memory of
+/// released objects to the system allocator immediately.
+pub fn set_free_list_limits(limits: &FreeListLimits) -> Result<()> {
+    use self::internal_prelude::c_int;
+    use hdf5_sys::h5::H5set_free_list_limits;
+    use std::convert::TryFrom;
+    let limit = |v: Option<usize>| -> Result<c_int> {
+        v.map_or(Ok(-1), |v| c_int::try_from(v).map_err(|_| format!("Invalid limit: {}", v).into()))
+    };
+    let l = limits;
+    let (reg_global, reg_list) = (limit(l.regular_global)?, limit(l.regular_list)?);
+    let (arr_global, arr_list) = (limit(l.array_global)?, limit(l.array_list)?);
+    let (blk_global, blk_list) = (limit(l.block_global)?, limit(l.block_list)?);
+    h5call!(H5set_free_list_limits(
+        reg_global, reg_list, arr_global, arr_list, blk_global, blk_list
+    ))
+    .and(Ok(()))
+}
+
+/// Total sizes of the internal free lists of the HDF5 library, in bytes.
+#[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeListSizes {
+    pub regular: usize,
+    pub array: usize,
+    pub block: usize,
+    pub factory: usize,
+}
+
+/// Returns the current total sizes of the internal free lists of the HDF5 library.
+#[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+pub fn free_list_sizes() -> Result<FreeListSizes> {
+    use self::internal_prelude::size_t;
+    use hdf5_sys::h5::H5get_free_list_sizes;
+    let mut v: (size_t, size_t, size_t, size_t) = (0, 0, 0, 0);
+    h5call!(H5get_free_list_sizes(&mut v.0, &mut v.1, &mut v.2, &mut v.3)).map(|_| FreeListSizes {
+        regular: v.0 as _,
+        array: v.1 as _,
+        block: v.2 as _,
+        factory: v.3 as _,
+    })
+}
+
 #[cfg(test)]
 pub mod tests {
+    use crate::internal_prelude::*;
     use crate::library_version;
 
     #[test]
     pub fn test_library_version() {
         assert!(library_version() >= (1, 8, 4));
     }
+
+    #[test]
+    pub fn test_garbage_collect() {
+        use crate::{garbage_collect, set_free_list_limits, FreeListLimits};
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f64>().shape((100, 100)).create("x").unwrap();
+            ds.write(&ndarray::Array2::<f64>::zeros((100, 100))).unwrap();
+        });
+        garbage_collect().unwrap();
+        #[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+        {
+            // hold the lock so that other threads can't refill the free lists in between
+            let sizes = sync(|| garbage_collect().and_then(|_| crate::free_list_sizes())).unwrap();
+            assert_eq!(sizes.regular, 0);
+            assert_eq!(sizes.array, 0);
+            assert_eq!(sizes.block, 0);
+        }
+
+        let none = FreeListLimits {
+            regular_global: None,
+            regular_list: None,
+            array_global: None,
+            array_list: None,
+            block_global: None,
+            block_list: None,
+        };
+        set_free_list_limits(&none).unwrap();
+        let too_large = FreeListLimits { block_list: Some(usize::MAX), ..none };
+        assert_err!(set_free_list_limits(&too_large), "Invalid limit");
+        set_free_list_limits(&FreeListLimits::default()).unwrap();
+    }
 }
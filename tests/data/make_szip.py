@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+"""Generates `szip.h5`, a minimal HDF5 file with an szip-compressed dataset.
+
+The file is written by hand (rather than via the HDF5 library) so that it can be created
+without szip encoding support. It contains a single 1-D dataset `/data` of 64 `u16` values
+(`i * 1000` for `i` in `0..64`), stored in one chunk compressed via the szip filter with
+entropy coding and 16 pixels per block; the chunk is encoded using the "no compression"
+option of the CCSDS 121.0 coder, which any szip decoder (including libaec) can read.
+
+The layout uses the original file format (superblock version 0, a symbol table for the
+root group, version 1 object headers and a version 1 B-tree chunk index).
+"""
+
+import struct
+
+UNDEF = 0xFFFFFFFFFFFFFFFF
+VALUES = [i * 1000 for i in range(64)]
+PIXELS_PER_BLOCK = 16
+# H5_SZIP_ALLOW_K13 | H5_SZIP_EC | H5_SZIP_LSB | H5_SZIP_RAW, as set by the library
+SZIP_MASK = 1 | 4 | 8 | 128
+
+
+def pad8(data):
+    return data + b'\0' * (-len(data) % 8)
+
+
+def szip_chunk(values):
+    bits = []
+    for i in range(0, len(values), PIXELS_PER_BLOCK):
+        bits.append('1111')  # block id of the "no compression" option for 9-16 bit samples
+        bits.extend(format(v, '016b') for v in values[i:i + PIXELS_PER_BLOCK])
+    bits = ''.join(bits)
+    bits += '0' * (-len(bits) % 8)
+    stream = int(bits, 2).to_bytes(len(bits) // 8, 'big')
+    return struct.pack('<I', 2 * len(values)) + stream
+
+
+def message(msg_type, data):
+    data = pad8(data)
+    return struct.pack('<HHB3x', msg_type, len(data), 0) + data
+
+
+def object_header(messages):
+    body = b''.join(messages)
+    return struct.pack('<BBHII4x', 1, 0, len(messages), 1, len(body)) + body
+
+
+def layout(addresses):
+    # fixed offsets of all structures
+    superblock = 0
+    root_ohdr = 96
+    root_ohdr_size = 16 + 8 + 16
+    heap = root_ohdr + root_ohdr_size
+    heap_data = heap + 32
+    group_btree = heap_data + 16
+    group_btree_size = 24 + 2 * 16 * 8 + (2 * 16 + 1) * 8
+    snod = group_btree + group_btree_size
+    snod_size = 8 + 2 * 4 * 40
+    dset_ohdr = snod + snod_size
+    return locals()
+
+
+def build():
+    chunk = szip_chunk(VALUES)
+    a = layout(None)
+
+    dataspace = struct.pack('<BBBx4xQQ', 1, 1, 1, len(VALUES), len(VALUES))
+    datatype = struct.pack('<B3sIHH', 0x10, b'\0\0\0', 2, 0, 16)
+    fill_value = struct.pack('<BBBB', 2, 3, 2, 0)
+    filters = struct.pack('<BB6x', 1, 1) + struct.pack('<HHHH', 4, 0, 0, 4)
+    filters += struct.pack('<4I', SZIP_MASK, PIXELS_PER_BLOCK, 16, len(VALUES))
+
+    def dset_header(chunk_btree):
+        layout_msg = struct.pack('<BBBQII', 3, 2, 2, chunk_btree, len(VALUES), 2)
+        return object_header([
+            message(0x0001, dataspace),
+            message(0x0003, datatype),
+            message(0x0005, fill_value),
+            message(0x0008, layout_msg),
+            message(0x000B, filters),
+        ])
+
+    dset_size = len(dset_header(0))
+    chunk_btree = a['dset_ohdr'] + dset_size
+    chunk_btree_size = 24 + 2 * 32 * 8 + (2 * 32 + 1) * 24
+    chunk_addr = chunk_btree + chunk_btree_size
+    eof = chunk_addr + len(chunk)
+
+    out = bytearray(eof)
+
+    def put(addr, data):
+        out[addr:addr + len(data)] = data
+
+    root_entry = struct.pack('<QQI4xQQ', 0, a['root_ohdr'], 1, a['group_btree'], a['heap'])
+    put(a['superblock'], b'\x89HDF\r\n\x1a\n' + bytes([0, 0, 0, 0, 0, 8, 8, 0]) +
+        struct.pack('<HHI', 4, 16, 0) + struct.pack('<QQQQ', 0, UNDEF, eof, UNDEF) + root_entry)
+
+    put(a['root_ohdr'], object_header([message(0x0011, struct.pack('<QQ', a['group_btree'],
+                                                                        a['heap']))]))
+    # the heap data holds the empty name of the root group and the name of the dataset;
+    # 1 is the marker of an empty free list
+    put(a['heap'], b'HEAP' + bytes([0, 0, 0, 0]) + struct.pack('<QQQ', 16, 1, a['heap_data']))
+    put(a['heap_data'], pad8(b'\0') + pad8(b'data\0'))
+
+    put(a['group_btree'], b'TREE' + struct.pack('<BBHQQ', 0, 0, 1, UNDEF, UNDEF) +
+        struct.pack('<QQQ', 0, a['snod'], 8))
+    put(a['snod'], b'SNOD' + struct.pack('<BBH', 1, 0, 1) +
+        struct.pack('<QQI4x16x', 8, a['dset_ohdr'], 0))
+
+    put(a['dset_ohdr'], dset_header(chunk_btree))
+    put(chunk_btree, b'TREE' + struct.pack('<BBHQQ', 1, 0, 1, UNDEF, UNDEF) +
+        struct.pack('<IIQQ', len(chunk), 0, 0, 0) + struct.pack('<Q', chunk_addr) +
+        struct.pack('<IIQQ', 0, 0, len(VALUES), 0))
+    put(chunk_addr, chunk)
+    return bytes(out)
+
+
+if __name__ == '__main__':
+    with open('szip.h5', 'wb') as f:
+        f.write(build())
@@ -379,3 +379,982 @@ fn test_byte_read_seek() -> hdf5::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_byte_reader_io_copy() -> hdf5::Result<()> {
+    let mut rng = SmallRng::seed_from_u64(42);
+    let file = new_in_memory_file()?;
+    // larger than the buffer used by io::copy(), so it's read in several hyperslabs
+    let arr: Array1<u8> = (0..100_000).map(|_| rng.gen()).collect();
+    let ds = file.new_dataset_builder().chunk(4096).with_data(&arr).create("blob")?;
+
+    let mut reader = ds.as_byte_reader()?;
+    let mut out = Vec::new();
+    assert_eq!(std::io::copy(&mut reader, &mut out).unwrap(), arr.len() as u64);
+    assert_eq!(out, ds.read_raw::<u8>()?);
+
+    // reading at or past the end yields no bytes
+    let mut buf = [0u8; 16];
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    reader.seek(SeekFrom::End(10)).unwrap();
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+    // resume from the middle
+    reader.seek(SeekFrom::Start(99_990)).unwrap();
+    let mut tail = Vec::new();
+    reader.read_to_end(&mut tail).unwrap();
+    assert_eq!(tail.as_slice(), arr.slice(s![99_990..]).as_slice().unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_byte_writer() -> hdf5::Result<()> {
+    use std::io::Write;
+
+    let mut rng = SmallRng::seed_from_u64(42);
+    let file = new_in_memory_file()?;
+    let bytes: Vec<u8> = (0..200_000).map(|_| rng.gen()).collect();
+
+    let ds = file.new_dataset::<u8>().chunk(1000).shape(0..).create("blob")?;
+    let mut writer = ds.as_byte_writer()?;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        // mix of small (buffered) and large (unbuffered) writes
+        let len = std::cmp::min(rng.gen_range(1..100_000), bytes.len() - pos);
+        writer.write_all(&bytes[pos..pos + len]).unwrap();
+        pos += len;
+    }
+    assert_eq!(writer.len(), bytes.len());
+    writer.flush().unwrap();
+    assert_eq!(ds.shape(), vec![bytes.len()]);
+    assert_eq!(ds.read_raw::<u8>()?, bytes);
+
+    // the remaining bytes get written out on drop; new writers append to the existing data
+    ds.as_byte_writer()?.write_all(b"tail").unwrap();
+    assert_eq!(ds.size(), bytes.len() + 4);
+    assert_eq!(ds.read_slice_1d::<u8, _>(bytes.len()..)?.to_vec(), b"tail");
+
+    let fixed = file.new_dataset::<u8>().shape(10).create("fixed")?;
+    assert!(fixed.as_byte_writer().is_err());
+    let two_dim = file.new_dataset::<u8>().shape((10, 0..)).create("2d")?;
+    assert!(two_dim.as_byte_writer().is_err());
+    let wide = file.new_dataset::<u16>().shape(0..).create("wide")?;
+    assert!(wide.as_byte_writer().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_byte_writer_failed_flush() -> hdf5::Result<()> {
+    use std::io::Write;
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<u8>().chunk(10).shape(0..=100).create("blob")?;
+    let mut writer = ds.as_byte_writer()?;
+    writer.write_all(&[1; 60]).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(ds.size(), 60);
+
+    // growing past the maximum size fails, but the buffered bytes must not be dropped
+    writer.write_all(&[2; 60]).unwrap();
+    assert!(writer.flush().is_err());
+    assert_eq!(writer.len(), 120);
+    assert!(writer.flush().is_err());
+    assert_eq!(writer.len(), 120);
+    assert_eq!(ds.size(), 60);
+    assert_eq!(ds.read_raw::<u8>()?, vec![1; 60]);
+    Ok(())
+}
+
+#[test]
+fn test_read_noop_conversion_bit_identical() -> hdf5::Result<()> {
+    // reading with identical in-file and in-memory types must preserve all bit patterns,
+    // including NaN payloads, infinities, signed zeros and subnormals
+    let mut rng = SmallRng::seed_from_u64(42);
+    let file = new_in_memory_file()?;
+    let mut bits: Vec<u64> = (0..100_000).map(|_| rng.gen()).collect();
+    bits.extend(&[0, 1 << 63, 1, 0x7ff0_0000_0000_0000, 0xfff0_0000_0000_0001, u64::MAX]);
+    let arr: Vec<f64> = bits.iter().map(|&b| f64::from_bits(b)).collect();
+
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+    assert!(ds.dtype()?.is::<f64>());
+    let out = ds.read_raw::<f64>()?;
+    assert_eq!(out.iter().map(|x| x.to_bits()).collect::<Vec<_>>(), bits);
+    let out = ds.as_reader().no_convert().read_slice_1d::<f64, _>(1000..2000)?;
+    assert_eq!(out.iter().map(|x| x.to_bits()).collect::<Vec<_>>(), &bits[1000..2000]);
+
+    // the same values stored with the opposite byte order go through the conversion path
+    let order = if cfg!(target_endian = "little") {
+        hdf5::datatype::ByteOrder::BigEndian
+    } else {
+        hdf5::datatype::ByteOrder::LittleEndian
+    };
+    let ds = file.new_dataset::<f64>().byte_order(order).shape(arr.len()).create("swapped")?;
+    ds.write(&arr)?;
+    assert_ne!(ds.dtype()?, hdf5::Datatype::from_type::<f64>()?);
+    let out = ds.read_raw::<f64>()?;
+    assert_eq!(out.iter().map(|x| x.to_bits()).collect::<Vec<_>>(), bits);
+    Ok(())
+}
+
+#[test]
+fn test_read_into_uninit() -> hdf5::Result<()> {
+    use std::mem::MaybeUninit;
+
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((30, 40), |(i, j)| (i * 40 + j) as f64);
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+
+    let mut buf = Array2::<MaybeUninit<f64>>::uninit((30, 40));
+    let out = ds.read_into_uninit(buf.view_mut())?;
+    assert_eq!(out, arr);
+    assert_eq!(out, ds.read_2d::<f64>()?);
+
+    let mut buf = Array2::<MaybeUninit<f64>>::uninit((40, 30));
+    assert!(ds.read_into_uninit(buf.view_mut()).is_err());
+    let mut buf = Array2::<MaybeUninit<f64>>::uninit((40, 30));
+    assert!(ds.read_into_uninit(buf.view_mut().reversed_axes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_with_conversion() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((10, 20), |(i, j)| (i as i16 - 5) * 1000 + j as i16);
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+    assert!(ds.dtype()?.is::<i16>());
+
+    // the in-memory type is chosen by the type parameter; HDF5 converts on read
+    let out = ds.read_dyn::<f64>()?;
+    assert_eq!(out.shape(), &[10, 20]);
+    assert_eq!(out, arr.mapv(f64::from).into_dyn());
+    assert_eq!(ds.read_slice_1d::<i64, _>(s![3, ..])?, arr.row(3).mapv(i64::from));
+    assert!(ds.as_reader().no_convert().read_2d::<f64>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_append_along_unlimited_axis() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    // `0..` denotes an unlimited axis with a current size of 0
+    let ds = file.new_dataset::<i32>().shape((100, 0..)).create("x")?;
+    assert!(ds.is_resizable());
+    assert!(ds.is_chunked());
+    assert_eq!(ds.shape(), vec![100, 0]);
+    assert_eq!(ds.space()?.maxdims(), vec![Some(100), None]);
+
+    for i in 0..3 {
+        let col = Array1::from_shape_fn(100, |j| (i * 100 + j) as i32);
+        ds.resize((100, i + 1))?;
+        ds.write_slice(&col, s![.., i])?;
+    }
+    assert_eq!(ds.shape(), vec![100, 3]);
+    let out = ds.read_2d::<i32>()?;
+    assert_eq!(out, Array2::from_shape_fn((100, 3), |(j, i)| (i * 100 + j) as i32));
+    assert!(ds.resize((101, 3)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_slices() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((100, 40), |(i, j)| (i * 1000 + j) as i32);
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+
+    let mut rng = SmallRng::seed_from_u64(42);
+    let slabs: Vec<_> = (0..50)
+        .map(|_| {
+            let (i, j) = (rng.gen_range(0..95), rng.gen_range(0..30));
+            let (n, m) = (rng.gen_range(0..5), rng.gen_range(1..10));
+            s![i..i + n, j..j + m;2]
+        })
+        .collect();
+    let slices = ds.read_slices::<i32, _, _>(slabs.iter().copied())?;
+    assert_eq!(slices.len(), 50);
+    for (slice, slab) in slices.iter().zip(&slabs) {
+        assert_eq!(slice, &ds.read_slice::<i32, _, IxDyn>(*slab)?);
+        assert_eq!(slice, &arr.slice(slab).into_dyn());
+    }
+
+    // overlapping slices, indices and points can be mixed
+    let points = Array2::from_shape_vec((3, 2), vec![5, 5, 0, 0, 5, 5]).unwrap();
+    let selections: Vec<hdf5::Selection> =
+        vec![(1..3, 2..4).into(), (2, 3..5).into(), points.into(), (50..50, ..).into()];
+    let slices = ds.read_slices::<i32, _, _>(selections)?;
+    assert_eq!(slices[0], arr.slice(s![1..3, 2..4]).into_dyn());
+    assert_eq!(slices[1], arr.slice(s![2, 3..5]).into_dyn());
+    assert_eq!(slices[2].as_slice().unwrap(), &[5005, 0, 5005]);
+    assert_eq!(slices[3].shape(), &[0, 40]);
+
+    assert!(ds.read_slices::<i32, hdf5::Selection, _>(vec![]).unwrap().is_empty());
+    assert!(ds.read_slices::<i32, _, _>(vec![(100, 0)]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_hyper_vector_size() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((200, 30), |(i, j)| (i * 100 + j) as u32);
+    let ds = file.new_dataset::<u32>().shape((200, 30)).create("x")?;
+    ds.as_writer().hyper_vector_size(1).write_slice(&arr, s![.., ..])?;
+    for &size in &[1, 1024, 1 << 16] {
+        let out =
+            ds.as_reader().hyper_vector_size(size).read_slice_2d::<u32, _>(s![1..;3, ..;2])?;
+        assert_eq!(out, arr.slice(s![1..;3, ..;2]));
+    }
+    assert_eq!(ds.read_2d::<u32>()?, arr);
+    Ok(())
+}
+
+#[test]
+fn test_write_slice_shape_mismatch() -> hdf5::Result<()> {
+    use ndarray::{Array3, Array4};
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<u8>().shape((4, 5, 6, 7)).create("x")?;
+
+    // off-by-one along the third axis
+    let err = ds.write_slice(&Array4::<u8>::zeros((2, 5, 3, 7)), s![1..3, .., 2..6, ..]);
+    let err = err.unwrap_err().to_string();
+    assert!(err.contains("along axis 2: memory has 3 elements, destination has 4"), "{}", err);
+    assert!(err.contains("[2, 5, 3, 7]") && err.contains("[2, 5, 4, 7]"), "{}", err);
+
+    // same number of elements, but a different shape
+    let err = ds.write_slice(&Array3::<u8>::zeros((3, 2, 7)), s![0..2, 0, 0..3, ..]);
+    assert!(err.unwrap_err().to_string().contains("along axis 0"));
+    let err = ds.write_slice(&ArrayD::<u8>::zeros(IxDyn(&[2, 7])), s![0, 0, 0..2, 0..6]);
+    assert!(err.unwrap_err().to_string().contains("along axis 1: memory has 7 elements"));
+
+    ds.write_slice(&Array4::<u8>::ones((2, 5, 4, 7)), s![1..3, .., 2..6, ..])?;
+    assert_eq!(ds.read_raw::<u8>()?.iter().map(|&x| x as usize).sum::<usize>(), 2 * 5 * 4 * 7);
+    Ok(())
+}
+
+#[test]
+fn test_data_transform() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array1::from_shape_fn(50, |i| (i * 100) as i32);
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+
+    let out = ds.as_reader().data_transform("x/100").read_1d::<f64>()?;
+    assert_eq!(out, Array1::from_shape_fn(50, |i| i as f64));
+    let out = ds.as_reader().data_transform("x/100").read_slice_1d::<f64, _>(10..20)?;
+    assert_eq!(out, Array1::from_shape_fn(10, |i| (i + 10) as f64));
+    // the stored values are unaffected
+    assert_eq!(ds.read_1d::<i32>()?, arr);
+
+    ds.as_writer().data_transform("x*10+5").write(&Array1::from_elem(50, 1))?;
+    assert_eq!(ds.read_1d::<i32>()?, Array1::from_elem(50, 15));
+
+    let attr = ds.new_attr::<i32>().create("a")?;
+    assert!(attr.as_reader().data_transform("x*2").read_scalar::<i32>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_renamed_fields_from_file_schema() -> hdf5::Result<()> {
+    #[allow(non_snake_case)]
+    #[derive(hdf5::H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Producer {
+        temperatureC: f32,
+        sampleId: u32,
+    }
+
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Sample {
+        #[hdf5(rename = "sampleId")]
+        id: u32,
+        #[hdf5(rename = "temperatureC")]
+        temperature: f64,
+    }
+
+    let file = new_in_memory_file()?;
+    let data: Vec<_> =
+        (0..10).map(|i| Producer { temperatureC: i as f32 / 2., sampleId: 100 + i }).collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("x")?;
+    match ds.dtype()?.to_descriptor()? {
+        TypeDescriptor::Compound(tp) => {
+            let names: Vec<_> = tp.fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, &["temperatureC", "sampleId"]);
+        }
+        _ => panic!("expected a compound type"),
+    }
+
+    // compound members are matched by name, regardless of their order and types
+    let out = ds.read_raw::<Sample>()?;
+    let expected: Vec<_> =
+        (0..10).map(|i| Sample { id: 100 + i, temperature: i as f64 / 2. }).collect();
+    assert_eq!(out, expected);
+
+    // the conversion is done in chunks which fit into the type conversion buffer
+    for &size in &[64, 4096, 16 << 20] {
+        assert_eq!(ds.as_reader().buffer_size(size).read_raw::<Sample>()?, expected);
+        ds.as_writer().buffer_size(size).write_raw(&data)?;
+    }
+    // the buffer must be able to hold at least one element
+    assert!(ds.as_reader().buffer_size(4).read_raw::<Sample>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_write_non_standard_layout() -> hdf5::Result<()> {
+    use ndarray::ShapeBuilder;
+
+    let file = new_in_memory_file()?;
+    let expected = Array2::from_shape_fn((30, 40), |(i, j)| (i * 100 + j) as i64);
+    let fortran = Array2::from_shape_fn((30, 40).f(), |(i, j)| (i * 100 + j) as i64);
+    assert!(!fortran.is_standard_layout());
+
+    let ds = file.new_dataset_builder().with_data(&fortran).create("f")?;
+    assert_eq!(ds.read_2d::<i64>()?, expected);
+
+    let ds = file.new_dataset::<i64>().shape((40, 30)).create("t")?;
+    ds.write(expected.t())?;
+    assert_eq!(ds.read_2d::<i64>()?, expected.t());
+
+    let ds = file.new_dataset::<i64>().shape((30, 40)).create("slices")?;
+    ds.write(&Array2::<i64>::zeros((30, 40)))?;
+    ds.write_slice(fortran.slice(s![..10, ..;2]), s![..10, ..20])?;
+    assert_eq!(ds.read_slice_2d::<i64, _>(s![..10, ..20])?, expected.slice(s![..10, ..;2]));
+
+    let v = ds.new_attr::<i64>().shape(20).create("strided")?;
+    v.write_raw(expected.row(3).slice(s![..;2]))?;
+    assert_eq!(v.read_raw::<i64>()?, expected.row(3).slice(s![..;2]).to_vec());
+    Ok(())
+}
+
+#[test]
+fn test_read_fixed_array_members_and_elements() -> hdf5::Result<()> {
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Particle {
+        id: u32,
+        pos: [f32; 3],
+    }
+
+    let file = new_in_memory_file()?;
+    let particles: Vec<_> =
+        (0..5).map(|i| Particle { id: i, pos: [i as f32, 0.5, -(i as f32)] }).collect();
+    let ds = file.new_dataset_builder().with_data(&particles[..]).create("particles")?;
+    assert_eq!(ds.read_raw::<Particle>()?, particles);
+
+    let vectors: Vec<[f32; 3]> = particles.iter().map(|p| p.pos).collect();
+    let ds = file.new_dataset_builder().with_data(&vectors[..]).create("vectors")?;
+    assert_eq!(ds.read_raw::<[f32; 3]>()?, vectors);
+    let expected = Array2::from_shape_fn((5, 3), |(i, j)| vectors[i][j]);
+    assert_eq!(ds.read_expanded::<f32>()?, expected.clone().into_dyn());
+    assert_eq!(ds.read_expanded::<f64>()?, expected.mapv(f64::from).into_dyn());
+
+    let nested = [[[1_u8, 2], [3, 4], [5, 6]], [[7, 8], [9, 10], [11, 12]]];
+    let ds = file.new_dataset_builder().with_data(&nested[..]).create("nested")?;
+    let out = ds.read_expanded::<u8>()?;
+    assert_eq!(out.shape(), &[2, 3, 2]);
+    assert_eq!(out.iter().cloned().collect::<Vec<_>>(), (1..=12).collect::<Vec<u8>>());
+
+    let ds = file.new_dataset::<f32>().shape(3).create("scalars")?;
+    assert!(ds.read_expanded::<f32>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_raw_id_roundtrip() -> hdf5::Result<()> {
+    use hdf5::sync::sync;
+    use hdf5_sys::h5i::H5Iinc_ref;
+
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((3, 4), |(i, j)| (i * 10 + j) as u16);
+    let ds = file.new_dataset_builder().with_data(&arr).create("data")?;
+
+    // borrowed id, as passed to C code
+    let id = ds.id();
+    assert_eq!(ds.refcount(), 1);
+
+    // the id handed back from C carries its own reference which the new wrapper owns
+    assert_eq!(sync(|| unsafe { H5Iinc_ref(id) }), 2);
+    let ds2 = unsafe { hdf5::from_id::<hdf5::Dataset>(id) }?;
+    assert_eq!(ds2.id(), id);
+    assert_eq!(ds2.read_2d::<u16>()?, arr);
+
+    drop(ds);
+    assert_eq!(ds2.refcount(), 1);
+    assert_eq!(ds2.read_2d::<u16>()?, arr);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std-types")]
+fn test_std_types_roundtrip() -> hdf5::Result<()> {
+    use std::convert::TryFrom;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
+
+    use hdf5::types::{Ipv4Octets, Ipv6Octets, RawDuration};
+
+    let file = new_in_memory_file()?;
+
+    let v4 = vec![Ipv4Addr::new(127, 0, 0, 1), Ipv4Addr::new(10, 1, 2, 3), Ipv4Addr::BROADCAST];
+    let octets: Vec<Ipv4Octets> = v4.iter().map(|&addr| addr.into()).collect();
+    let ds = file.new_dataset_builder().with_data(&octets[..]).create("ipv4")?;
+    let read: Vec<Ipv4Addr> = ds.read_raw::<Ipv4Octets>()?.into_iter().map(Into::into).collect();
+    assert_eq!(read, v4);
+    assert_eq!(ds.read_raw::<[u8; 4]>()?[1], [10, 1, 2, 3]);
+
+    let v6 = vec![Ipv6Addr::LOCALHOST, Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x42)];
+    let octets: Vec<Ipv6Octets> = v6.iter().map(|&addr| addr.into()).collect();
+    let ds = file.new_dataset_builder().with_data(&octets[..]).create("ipv6")?;
+    let read: Vec<Ipv6Addr> = ds.read_raw::<Ipv6Octets>()?.into_iter().map(Into::into).collect();
+    assert_eq!(read, v6);
+    assert_eq!(ds.read_raw::<[u8; 16]>()?[0], Ipv6Addr::LOCALHOST.octets());
+
+    let durations = vec![Duration::new(0, 0), Duration::new(5, 123), Duration::from_millis(1500)];
+    let raw: Vec<RawDuration> = durations.iter().map(|&d| d.into()).collect();
+    let ds = file.new_dataset_builder().with_data(&raw[..]).create("durations")?;
+    let read = ds.read_raw::<RawDuration>()?;
+    let read: Vec<Duration> =
+        read.into_iter().map(Duration::try_from).collect::<Result<_, _>>().unwrap();
+    assert_eq!(read, durations);
+
+    // the compound members can be read by name into a user-defined struct
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Reordered {
+        nanos: u32,
+        secs: u64,
+    }
+    assert_eq!(ds.read_raw::<Reordered>()?[1], Reordered { nanos: 123, secs: 5 });
+
+    // out-of-range nanoseconds (e.g. written by other tools) are reported as errors
+    let invalid = [Reordered { nanos: 1_500_000_000, secs: 1 }];
+    let ds = file.new_dataset_builder().with_data(&invalid[..]).create("invalid")?;
+    let read = ds.read_raw::<RawDuration>()?;
+    assert_eq!(read, [RawDuration { secs: 1, nanos: 1_500_000_000 }]);
+    assert!(Duration::try_from(read[0]).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn test_timestamps_roundtrip() -> hdf5::Result<()> {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use hdf5::types::{Timestamp, VarLenUnicode};
+
+    let file = new_in_memory_file()?;
+    let start = Utc.with_ymd_and_hms(2020, 2, 29, 23, 59, 59).unwrap();
+    let datetimes: Vec<DateTime<Utc>> =
+        (0..10).map(|i| start + Duration::nanoseconds(i * 1_000_000_007 - 3)).collect();
+    let data: Vec<_> = datetimes.iter().map(|dt| Timestamp::from_datetime(dt).unwrap()).collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("events")?;
+    let units: VarLenUnicode = Timestamp::UNITS.parse().unwrap();
+    ds.new_attr::<VarLenUnicode>().create("units")?.write_scalar(&units)?;
+
+    let out: Vec<DateTime<Utc>> = ds.read_raw::<Timestamp>()?.into_iter().map(Into::into).collect();
+    assert_eq!(out, datetimes);
+    // stored as plain 64-bit integers
+    assert_eq!(ds.read_raw::<i64>()?[0], start.timestamp_nanos_opt().unwrap() - 3);
+    assert_eq!(ds.attr("units")?.read_scalar::<VarLenUnicode>()?.as_str(), Timestamp::UNITS);
+    Ok(())
+}
+
+#[test]
+fn test_read_nested_varlen() -> hdf5::Result<()> {
+    use hdf5::types::dyn_value::{DynInteger, DynScalar};
+    use hdf5::types::{DynValue, VarLenArray};
+
+    // a variable-length array of `VarLenArray<i32>`, laid out the same way as `hvl_t`
+    #[repr(C)]
+    struct Ragged {
+        len: usize,
+        ptr: *const VarLenArray<i32>,
+    }
+
+    unsafe impl H5Type for Ragged {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::VarLenArray(Box::new(VarLenArray::<i32>::type_descriptor()))
+        }
+    }
+
+    fn to_vec(value: DynValue) -> Vec<Vec<i32>> {
+        match value {
+            DynValue::Array(rows) => rows
+                .iter()
+                .map(|row| match row {
+                    DynValue::Array(row) => row
+                        .iter()
+                        .map(|x| match x {
+                            DynValue::Scalar(DynScalar::Integer(DynInteger::Int32(x))) => x,
+                            x => panic!("expected i32, got {:?}", x),
+                        })
+                        .collect(),
+                    row => panic!("expected an array, got {:?}", row),
+                })
+                .collect(),
+            value => panic!("expected an array, got {:?}", value),
+        }
+    }
+
+    let data = vec![
+        vec![vec![1], vec![2, 3]],
+        vec![],
+        vec![vec![], vec![4, 5, 6], vec![7]],
+        vec![(0..100).collect()],
+    ];
+    let inner: Vec<Vec<VarLenArray<i32>>> = data
+        .iter()
+        .map(|rows| rows.iter().map(|row| VarLenArray::from_slice(row)).collect())
+        .collect();
+    let outer: Vec<_> =
+        inner.iter().map(|rows| Ragged { len: rows.len(), ptr: rows.as_ptr() }).collect();
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<Ragged>().shape(outer.len()).create("ragged")?;
+    ds.write_raw(&outer)?;
+
+    for _ in 0..10 {
+        // all nested buffers are owned by the values and are freed when they are dropped
+        let values = ds.read_raw_dyn()?;
+        assert_eq!(values.len(), data.len());
+        assert_eq!(values[0].type_descriptor(), &Ragged::type_descriptor());
+        let copies = values.clone();
+        drop(values);
+        let out: Vec<_> = copies.iter().map(|value| to_vec(value.get())).collect();
+        assert_eq!(out, data);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_big_endian_dataset() -> hdf5::Result<()> {
+    use hdf5::datatype::ByteOrder;
+    use hdf5::globals::H5T_STD_I32BE;
+    use hdf5::sync::sync;
+    use hdf5_sys::{h5d::H5Dread, h5p::H5P_DEFAULT, h5s::H5S_ALL};
+
+    let file = new_in_memory_file()?;
+    let data: Vec<i32> = vec![1, -2, 0x0102_0304, i32::MIN];
+    let ds = file
+        .new_dataset_builder()
+        .byte_order(ByteOrder::BigEndian)
+        .with_data(&data)
+        .create("be")?;
+    assert_eq!(ds.dtype()?.byte_order(), ByteOrder::BigEndian);
+    assert_eq!(ds.read_raw::<i32>()?, data);
+    let ds = file.new_dataset::<i32>().byte_order(ByteOrder::LittleEndian).shape(4).create("le")?;
+    ds.write_raw(&data)?;
+    assert_eq!(ds.dtype()?.byte_order(), ByteOrder::LittleEndian);
+
+    // reading the big-endian dataset with an identical memory type yields the stored bytes
+    let mut raw = vec![0_u8; data.len() * 4];
+    let ds = file.dataset("be")?;
+    sync(|| unsafe {
+        H5Dread(ds.id(), *H5T_STD_I32BE, H5S_ALL, H5S_ALL, H5P_DEFAULT, raw.as_mut_ptr().cast())
+    });
+    let expected: Vec<u8> = data.iter().flat_map(|x| x.to_be_bytes()).collect();
+    assert_eq!(raw, expected);
+    Ok(())
+}
+
+#[test]
+fn test_tagged_union_roundtrip() -> hdf5::Result<()> {
+    #[derive(hdf5::H5Type, Clone, Copy)]
+    #[repr(C)]
+    union Value {
+        int: i64,
+        float: f64,
+        pair: [u16; 2],
+    }
+
+    #[derive(hdf5::H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Record {
+        tag: u8,
+        value: Value,
+    }
+
+    let file = new_in_memory_file()?;
+    let data = vec![
+        Record { tag: 0, value: Value { int: -42 } },
+        Record { tag: 1, value: Value { float: 2.5 } },
+        Record { tag: 2, value: Value { pair: [7, 9] } },
+    ];
+    let ds = file.new_dataset_builder().with_data(&data).create("records")?;
+    let td = ds.dtype()?.to_descriptor()?;
+    match td {
+        TypeDescriptor::Compound(ref c) => {
+            assert_eq!(c.fields.len(), 2);
+            assert_eq!(c.fields[1].name, "value");
+            assert_eq!(c.fields[1].ty.size(), std::mem::size_of::<Value>());
+        }
+        _ => panic!("expected a compound type, got {:?}", td),
+    }
+
+    let out = ds.read_raw::<Record>()?;
+    assert_eq!(out.len(), data.len());
+    for record in &out {
+        // the active member of the union is determined by the tag
+        unsafe {
+            match record.tag {
+                0 => assert_eq!(record.value.int, -42),
+                1 => assert_eq!(record.value.float, 2.5),
+                2 => assert_eq!(record.value.pair, [7, 9]),
+                tag => panic!("unexpected tag: {}", tag),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_read_field() -> hdf5::Result<()> {
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Wide {
+        id: u32,
+        flags: [u8; 5],
+        x: f64,
+        y: f32,
+        label: hdf5::types::FixedAscii<12>,
+        z: i64,
+    }
+
+    let file = new_in_memory_file()?;
+    let data: Vec<_> = (0..20)
+        .map(|i| Wide {
+            id: i,
+            flags: [i as u8; 5],
+            x: f64::from(i) * 1.5 - 3.0,
+            y: -(i as f32),
+            label: hdf5::types::FixedAscii::from_ascii(format!("row{}", i).as_bytes()).unwrap(),
+            z: i64::from(i) << 40,
+        })
+        .collect();
+    let arr = Array2::from_shape_vec((4, 5), data).unwrap();
+    let ds = file.new_dataset_builder().with_data(&arr).create("wide")?;
+
+    let full = ds.read_2d::<Wide>()?;
+    let x = ds.read_field::<f64>("x")?;
+    assert_eq!(x.shape(), &[4, 5]);
+    assert_eq!(x, full.map(|r| r.x).into_dyn());
+    // the member is converted to the requested type while reading
+    assert_eq!(ds.read_field::<f64>("id")?, full.map(|r| f64::from(r.id)).into_dyn());
+    assert_eq!(ds.read_field::<i64>("z")?[[3, 4]], 19 << 40);
+
+    let err = ds.read_field::<f64>("missing").unwrap_err();
+    assert!(err.to_string().contains("no member named \"missing\""));
+    let ds = file.new_dataset_builder().with_data(&[1.0_f64, 2.0]).create("plain")?;
+    assert!(ds.read_field::<f64>("x").unwrap_err().to_string().contains("compound"));
+    Ok(())
+}
+
+#[test]
+fn test_read_enum_raw() -> hdf5::Result<()> {
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(i16)]
+    enum Status {
+        Off = -1,
+        On = 10,
+        Error = 300,
+    }
+
+    #[derive(hdf5::H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        High = 200,
+    }
+
+    let file = new_in_memory_file()?;
+    let data = Array2::from_shape_vec(
+        (2, 3),
+        vec![Status::On, Status::Off, Status::Error, Status::Error, Status::On, Status::On],
+    )
+    .unwrap();
+    let ds = file.new_dataset_builder().with_data(&data).create("status")?;
+    let (members, values) = ds.read_enum_raw()?;
+    assert_eq!(
+        members,
+        vec![("Off".to_owned(), -1), ("On".to_owned(), 10), ("Error".to_owned(), 300)]
+    );
+    assert_eq!(values.shape(), &[2, 3]);
+    assert_eq!(values, data.map(|&s| s as i64).into_dyn());
+
+    let ds = file.new_dataset_builder().with_data(&[Level::High, Level::Low]).create("level")?;
+    let (members, values) = ds.read_enum_raw()?;
+    assert_eq!(members, vec![("Low".to_owned(), 1), ("High".to_owned(), 200)]);
+    assert_eq!(values.into_raw_vec(), vec![200, 1]);
+
+    let ds = file.new_dataset_builder().with_data(&[1_i16, 2]).create("plain")?;
+    assert!(ds.read_enum_raw().unwrap_err().to_string().contains("Expected an enum type"));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "1.10.0")]
+fn test_dont_filter_partial_chunks() -> hdf5::Result<()> {
+    use hdf5::plist::dataset_create::ChunkOpts;
+
+    let file = new_in_memory_file()?;
+    // the shape is not a multiple of the chunk shape, so there are partial edge chunks
+    let data = Array2::from_shape_fn((10, 7), |(i, j)| (i * 7 + j) as i32);
+    let mut builder = file.new_dataset_builder();
+    if hdf5::filters::deflate_available() {
+        builder = builder.deflate(6);
+    }
+    let ds = builder
+        .shuffle()
+        .chunk((4, 3))
+        .chunk_opts(ChunkOpts::DONT_FILTER_PARTIAL_CHUNKS)
+        .with_data(&data)
+        .create("partial")?;
+    assert_eq!(ds.dcpl()?.chunk_opts(), Some(ChunkOpts::DONT_FILTER_PARTIAL_CHUNKS));
+    assert_eq!(ds.read_2d::<i32>()?, data);
+    assert_eq!(ds.read_slice_2d::<i32, _>(s![8.., 6..])?, data.slice(s![8.., 6..]));
+
+    let ds = file.new_dataset::<i32>().chunk((4, 3)).shape((10, 7)).create("default")?;
+    assert_eq!(ds.dcpl()?.chunk_opts(), Some(ChunkOpts::empty()));
+    Ok(())
+}
+
+#[test]
+fn test_read_fixed_ndim() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr1 = Array1::from_shape_fn(4, |i| i as u16);
+    let arr2 = Array2::from_shape_fn((2, 3), |(i, j)| (i * 3 + j) as u16);
+    let arr3 = ndarray::Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 12 + j * 4 + k) as u16);
+    let ds1 = file.new_dataset_builder().with_data(&arr1).create("d1")?;
+    let ds2 = file.new_dataset_builder().with_data(&arr2).create("d2")?;
+    let ds3 = file.new_dataset_builder().with_data(&arr3).create("d3")?;
+
+    assert_eq!(ds1.read_1d::<u16>()?, arr1);
+    assert_eq!(ds2.read_2d::<u16>()?, arr2);
+    assert_eq!(ds3.read_3d::<u16>()?, arr3);
+    assert_eq!(ds3.as_reader().read_3d::<f64>()?, arr3.mapv(f64::from));
+
+    let expect_mismatch = |result: hdf5::Result<()>, expected: usize, actual: usize| {
+        let msg = format!("ndim mismatch: expected {}, got {}", expected, actual);
+        assert_eq!(result.unwrap_err().to_string(), msg);
+    };
+    expect_mismatch(ds2.read_1d::<u16>().map(|_| ()), 1, 2);
+    expect_mismatch(ds3.read_1d::<u16>().map(|_| ()), 1, 3);
+    expect_mismatch(ds1.read_2d::<u16>().map(|_| ()), 2, 1);
+    expect_mismatch(ds3.read_2d::<u16>().map(|_| ()), 2, 3);
+    expect_mismatch(ds1.read_3d::<u16>().map(|_| ()), 3, 1);
+    expect_mismatch(ds2.read_3d::<u16>().map(|_| ()), 3, 2);
+    Ok(())
+}
+
+#[test]
+fn test_read_slice_fixed_ndim() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = ndarray::Array3::from_shape_fn((3, 4, 5), |(i, j, k)| (i * 20 + j * 5 + k) as i32);
+    let ds = file.new_dataset_builder().with_data(&arr).create("d")?;
+
+    let out: Array2<i32> = ds.read_slice_2d(s![1, 1..3, ..;2])?;
+    assert_eq!(out, arr.slice(s![1, 1..3, ..;2]));
+    let out: Array2<i32> = ds.read_slice_2d(s![.., 2, 1..4])?;
+    assert_eq!(out, arr.slice(s![.., 2, 1..4]));
+    assert_eq!(ds.as_reader().read_slice_2d::<f64, _>(s![2, .., 4..])?.dim(), (4, 1));
+    assert_eq!(ds.read_slice_1d::<i32, _>(s![0, 3, ..])?, arr.slice(s![0, 3, ..]));
+
+    // the rank of the result (with the indexed axes dropped) must match the array
+    let expect_mismatch = |result: hdf5::Result<()>, expected: usize, actual: usize| {
+        let msg = format!("Selection ndim ({}) != array ndim ({})", actual, expected);
+        assert_eq!(result.unwrap_err().to_string(), msg);
+    };
+    expect_mismatch(ds.read_slice_2d::<i32, _>(s![.., .., ..]).map(|_| ()), 2, 3);
+    expect_mismatch(ds.read_slice_2d::<i32, _>(s![0, 0, ..]).map(|_| ()), 2, 1);
+    expect_mismatch(ds.read_slice_1d::<i32, _>(s![0, .., ..]).map(|_| ()), 1, 2);
+    // the selection itself must match the rank of the dataset
+    let err = ds.read_slice_2d::<i32, _>(s![.., ..]).unwrap_err();
+    assert_eq!(err.to_string(), "Slice ndim (2) != shape ndim (3)");
+    let err = ds.read_slice_1d::<i32, _>(s![0, 0, 0, ..]).unwrap_err();
+    assert_eq!(err.to_string(), "Slice ndim (4) != shape ndim (3)");
+    Ok(())
+}
+
+#[test]
+fn test_write_strided_views() -> hdf5::Result<()> {
+    use ndarray::ShapeBuilder;
+
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((10, 8), |(i, j)| (i * 8 + j) as i64);
+    let arr3 = ndarray::Array3::from_shape_fn((4, 5, 6), |(i, j, k)| (i * 30 + j * 6 + k) as f32);
+
+    // views sliced with steps are written directly from memory via a memory selection
+    let views = [
+        arr.slice(s![..;3, ..;2]),
+        arr.slice(s![1..9;2, 3..]),
+        arr.slice(s![.., 5..6]),
+        arr.slice(s![2..3, ..;3]),
+    ];
+    for (i, view) in views.iter().enumerate() {
+        assert!(!view.is_standard_layout());
+        let ds = file.new_dataset::<i64>().shape(view.shape()).create(&*format!("v{}", i))?;
+        ds.write(view)?;
+        assert_eq!(ds.read_2d::<i64>()?, view);
+    }
+    let view = arr3.slice(s![1..;2, ..;2, 1..5;3]);
+    let ds = file.new_dataset_builder().with_data(view).create("v3d")?;
+    assert_eq!(ds.read_dyn::<f32>()?, view.into_dyn());
+    let ds = file.new_dataset::<f32>().shape((3, 4, 6)).create("slices")?;
+    let view = view.index_axis_move(ndarray::Axis(0), 1);
+    ds.write_slice(view, s![1, 1.., 2..4])?;
+    assert_eq!(ds.read_slice_2d::<f32, _>(s![1, 1.., 2..4])?, view);
+    let ds = file.new_dataset::<i64>().shape(5).create("raw")?;
+    ds.write_raw(arr.slice(s![..5, 7]))?;
+    assert_eq!(ds.read_raw::<i64>()?, vec![7, 15, 23, 31, 39]);
+
+    // views whose elements aren't in row-major order are copied before writing
+    let views = [arr.t(), arr.slice(s![..;-1, ..]), arr.slice(s![..;2, ..;-3])];
+    for (i, view) in views.iter().enumerate() {
+        let ds = file.new_dataset::<i64>().shape(view.shape()).create(&*format!("c{}", i))?;
+        ds.write(view)?;
+        assert_eq!(ds.read_2d::<i64>()?, view);
+    }
+    let f = Array2::from_shape_vec((3, 4).f(), (0..12_i64).collect()).unwrap();
+    let ds = file.new_dataset_builder().with_data(&f).create("fortran")?;
+    assert_eq!(ds.read_2d::<i64>()?, f);
+
+    // attributes don't support memory selections
+    let attr = file.new_attr::<i64>().shape((4, 4)).create("attr")?;
+    attr.write(views[0].slice(s![..;2, ..;3]))?;
+    assert_eq!(attr.read_2d::<i64>()?, arr.t().slice(s![..;2, ..;3]));
+    let attr = file.new_attr::<i64>().shape((4, 4)).create("strided")?;
+    attr.write(arr.slice(s![..;3, ..;2]))?;
+    assert_eq!(attr.read_2d::<i64>()?, arr.slice(s![..;3, ..;2]));
+    Ok(())
+}
+
+#[test]
+fn test_read_any() -> hdf5::Result<()> {
+    use hdf5::types::{FixedAscii, VarLenUnicode};
+    use hdf5::AnyArray;
+
+    let file = new_in_memory_file()?;
+    let floats = Array2::from_shape_fn((2, 3), |(i, j)| i as f64 - j as f64 * 0.25);
+    let ds = file.new_dataset_builder().with_data(&floats).create("floats")?;
+    match ds.read_any()? {
+        AnyArray::F64(arr) => assert_eq!(arr, floats.into_dyn()),
+        arr => panic!("expected f64 array, got {:?}", arr),
+    }
+    let ds = file.new_dataset_builder().with_data(&[1_u16, 2, 3]).create("ints")?;
+    let arr = ds.read_any()?;
+    assert_eq!(arr.shape(), &[3]);
+    assert!(matches!(arr, AnyArray::U16(ref a) if a.as_slice() == Some(&[1, 2, 3][..])));
+
+    let words = ["alpha", "β", ""];
+    let data: Vec<VarLenUnicode> = words.iter().map(|s| s.parse().unwrap()).collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("varlen")?;
+    match ds.read_any()? {
+        AnyArray::String(arr) => assert_eq!(arr.into_raw_vec(), words),
+        arr => panic!("expected string array, got {:?}", arr),
+    }
+    let data: Vec<FixedAscii<6>> =
+        ["ab", "cdefgh", ""].iter().map(|s| FixedAscii::from_ascii(s).unwrap()).collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("fixed")?;
+    match ds.read_any()? {
+        AnyArray::String(arr) => assert_eq!(arr.into_raw_vec(), ["ab", "cdefgh", ""]),
+        arr => panic!("expected string array, got {:?}", arr),
+    }
+
+    // other types are read as dynamic values
+    let ds = file.new_dataset_builder().with_data(&[[1_i32, 2], [3, 4], [5, 6]]).create("arr")?;
+    match ds.read_any()? {
+        AnyArray::Other(arr) => {
+            assert_eq!(arr.shape(), &[3]);
+            assert_eq!(arr[1].type_descriptor(), &<[i32; 2]>::type_descriptor());
+            assert_eq!(arr[2].clone().cast::<[i32; 2]>().unwrap(), [5, 6]);
+        }
+        arr => panic!("expected dynamic values, got {:?}", arr),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_mismatch_errors() -> hdf5::Result<()> {
+    use hdf5::types::VarLenUnicode;
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset_builder().with_data(&[1_i32, 2, 3]).create("ints")?;
+
+    // wrong type (no conversion path between the stored and the requested types)
+    assert!(ds.read_raw::<VarLenUnicode>().is_err());
+    assert!(ds.read_raw::<(i32, f64)>().is_err());
+    let strings: Vec<VarLenUnicode> = ["a", "b", "c"].iter().map(|s| s.parse().unwrap()).collect();
+    assert!(ds.write(&strings).is_err());
+
+    // wrong rank or shape
+    assert!(ds.read_2d::<i32>().is_err());
+    assert!(ds.read_scalar::<i32>().is_err());
+    assert!(ds.write(&Array2::<i32>::zeros((1, 3))).is_err());
+    assert!(ds.write(&[1_i32, 2]).is_err());
+    assert!(ds.write_raw(&[1_i32, 2, 3, 4]).is_err());
+    assert!(ds.write_scalar(&1_i32).is_err());
+    assert!(ds.write_slice(&[1_i32, 2], 2..).is_err());
+
+    assert_eq!(ds.read_raw::<i32>()?, [1, 2, 3]);
+    Ok(())
+}
+
+#[test]
+fn test_write_string_too_long() -> hdf5::Result<()> {
+    use hdf5::types::{FixedAscii, FixedUnicode};
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<FixedAscii<4>>().shape(2).create("fixed")?;
+    let to_fixed = |words: &[&str]| -> Vec<FixedAscii<8>> {
+        words.iter().map(|s| FixedAscii::from_ascii(s).unwrap()).collect()
+    };
+    let read_words = || -> hdf5::Result<Vec<String>> {
+        Ok(ds.read_raw::<FixedAscii<4>>()?.iter().map(|s| s.as_str().to_owned()).collect())
+    };
+
+    // longer string types can be written as long as the strings fit
+    ds.write(&to_fixed(&["abcd", ""]))?;
+    assert_eq!(read_words()?, ["abcd", ""]);
+
+    // otherwise, an error is returned instead of silently truncating the strings
+    let too_long = to_fixed(&["abc", "abcde"]);
+    let err = ds.write(&too_long).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "String of length 5 does not fit into the destination string type of length 4"
+    );
+    assert!(ds.write_raw(&too_long).is_err());
+    assert!(ds.write_slice(&too_long[1..], 1..).is_err());
+    assert_eq!(read_words()?, ["abcd", ""]);
+
+    let attr = ds.new_attr::<FixedUnicode<4>>().create("name")?;
+    attr.write_scalar(&"αβ".parse::<FixedUnicode<8>>().unwrap())?;
+    assert!(attr.write_scalar(&"αβγ".parse::<FixedUnicode<8>>().unwrap()).is_err());
+    assert_eq!(attr.read_scalar::<FixedUnicode<4>>()?.as_str(), "αβ");
+    Ok(())
+}
+
+#[test]
+fn test_write_chunked() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((10, 7), |(i, j)| (i * 7 + j) as f64 / 4.);
+
+    // the chunks don't divide the shape evenly, and the data is converted to f32
+    let ds = file.new_dataset::<f32>().chunk((4, 3)).shape((10, 7)).create("chunked")?;
+    ds.write_chunked(&arr)?;
+    let single = file.new_dataset::<f32>().chunk((4, 3)).shape((10, 7)).create("single")?;
+    single.write(&arr)?;
+    assert_eq!(ds.read_2d::<f32>()?, single.read_2d::<f32>()?);
+    assert_eq!(ds.read_2d::<f64>()?, arr);
+
+    // non-standard layouts are supported as well
+    let ds = file.new_dataset::<f64>().chunk((3, 4)).shape((7, 10)).create("transposed")?;
+    ds.write_chunked(arr.t())?;
+    assert_eq!(ds.read_2d::<f64>()?, arr.t());
+
+    // datasets which are not chunked are written at once
+    let ds = file.new_dataset::<f64>().shape((10, 7)).create("contiguous")?;
+    ds.write_chunked(&arr)?;
+    assert_eq!(ds.read_2d::<f64>()?, arr);
+
+    let ds = file.new_dataset::<f64>().chunk((4, 3)).shape((10, 8)).create("mismatch")?;
+    assert!(ds.write_chunked(&arr).is_err());
+    Ok(())
+}
@@ -133,6 +133,88 @@ pub fn test_datatype_roundtrip() {
     check_roundtrip!(E, e_desc);
 }
 
+#[test]
+pub fn test_compound_padding() {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct A {
+        a: u8,
+        b: u32,
+    }
+    let dt = Datatype::from_type::<A>().unwrap();
+    match dt.to_descriptor().unwrap() {
+        TD::Compound(tp) => {
+            assert_eq!(tp.size, 8);
+            assert_eq!(tp.padding(), vec![(1, 3)]);
+            let dt2 = Datatype::from_descriptor(&TD::Compound(tp)).unwrap();
+            assert_eq!(dt2, dt);
+        }
+        _ => panic!("expected a compound type"),
+    }
+}
+
+#[test]
+pub fn test_encode_decode() -> hdf5::Result<()> {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct Inner {
+        a: [u16; 3],
+        b: VarLenUnicode,
+    }
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct Outer {
+        x: f64,
+        inner: Inner,
+        values: VarLenArray<i32>,
+        name: FixedAscii<8>,
+    }
+
+    let dt = Datatype::from_type::<Outer>()?;
+    let encoded = dt.encode()?;
+    assert!(!encoded.is_empty());
+    let decoded = Datatype::decode(&encoded)?;
+    assert_eq!(decoded, dt);
+    assert_eq!(decoded.to_descriptor()?, Outer::type_descriptor());
+    assert_eq!(decoded.encode()?, encoded);
+    assert_ne!(Datatype::decode(Datatype::from_type::<u8>()?.encode()?)?, dt);
+    assert!(Datatype::decode(Vec::<u8>::new()).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_member_index() -> hdf5::Result<()> {
+    #[derive(H5Type)]
+    #[repr(C)]
+    struct Row {
+        id: u8,
+        value: f64,
+        label: FixedAscii<4>,
+    }
+
+    let dt = Datatype::from_type::<Row>()?;
+    assert_eq!(dt.num_members(), 3);
+    for (i, &name) in ["id", "value", "label"].iter().enumerate() {
+        assert_eq!(dt.member_index(name), Some(i));
+        assert_eq!(dt.member_name(i).as_deref(), Some(name));
+    }
+    if let TD::Compound(tp) = dt.to_descriptor()? {
+        for field in &tp.fields {
+            assert_eq!(dt.member_index(&field.name), Some(field.index));
+        }
+    } else {
+        panic!("expected a compound datatype");
+    }
+    assert_eq!(dt.member_index("missing"), None);
+    assert_eq!(dt.member_name(3), None);
+
+    let dt = Datatype::from_type::<i32>()?;
+    assert_eq!(dt.num_members(), 0);
+    assert_eq!(dt.member_index("id"), None);
+    assert_eq!(dt.member_name(0), None);
+    Ok(())
+}
+
 #[test]
 pub fn test_invalid_datatype() {
     assert_err!(from_id::<Datatype>(H5I_INVALID_HID), "Invalid handle id");
@@ -148,3 +230,56 @@ pub fn test_eq() {
 pub fn test_debug() {
     assert_eq!(format!("{:?}", Datatype::from_type::<u32>().unwrap()), "<HDF5 datatype>");
 }
+
+#[test]
+pub fn test_type_class() -> hdf5::Result<()> {
+    use hdf5::datatype::TypeClass as TC;
+    use hdf5_sys::h5t::{H5T_class_t, H5Tcopy, H5Tcreate, H5T_NATIVE_B8, H5T_STD_REF_OBJ};
+
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(u8)]
+    enum Color {
+        Red = 1,
+        Blue = 2,
+    }
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    let class = |desc: TD| Datatype::from_descriptor(&desc).unwrap().type_class().unwrap();
+    assert_eq!(class(i16::type_descriptor()), TC::Integer { signed: true, size: 2 });
+    assert_eq!(class(u64::type_descriptor()), TC::Integer { signed: false, size: 8 });
+    assert_eq!(class(f32::type_descriptor()), TC::Float { size: 4 });
+    assert_eq!(class(bool::type_descriptor()), TC::Enum);
+    assert_eq!(class(Color::type_descriptor()), TC::Enum);
+    assert_eq!(class(Point::type_descriptor()), TC::Compound);
+    assert_eq!(class(<[u16; 3]>::type_descriptor()), TC::Array);
+    assert_eq!(class(VarLenArray::<i8>::type_descriptor()), TC::VarLen);
+    assert_eq!(
+        class(FixedAscii::<8>::type_descriptor()),
+        TC::String { variable: false, is_utf8: false }
+    );
+    assert_eq!(
+        class(FixedUnicode::<8>::type_descriptor()),
+        TC::String { variable: false, is_utf8: true }
+    );
+    assert_eq!(
+        class(VarLenAscii::type_descriptor()),
+        TC::String { variable: true, is_utf8: false }
+    );
+    assert_eq!(
+        class(VarLenUnicode::type_descriptor()),
+        TC::String { variable: true, is_utf8: true }
+    );
+
+    // types with no Rust equivalent can be classified as well
+    let raw = |id| from_id::<Datatype>(id).unwrap().type_class().unwrap();
+    assert_eq!(raw(hdf5::sync::sync(|| unsafe { H5Tcopy(*H5T_NATIVE_B8) })), TC::Bitfield);
+    assert_eq!(raw(hdf5::sync::sync(|| unsafe { H5Tcopy(*H5T_STD_REF_OBJ) })), TC::Reference);
+    let opaque = hdf5::sync::sync(|| unsafe { H5Tcreate(H5T_class_t::H5T_OPAQUE, 4) });
+    assert_eq!(raw(opaque), TC::Opaque);
+    Ok(())
+}
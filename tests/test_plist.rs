@@ -3,6 +3,7 @@ use std::str::FromStr;
 
 use hdf5::dataset::*;
 use hdf5::file::*;
+use hdf5::plist::group_create::*;
 use hdf5::plist::*;
 
 macro_rules! test_pl {
@@ -560,6 +561,15 @@ fn test_fapl_set_evict_on_close() -> hdf5::Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(any(all(feature = "1.10.7", not(feature = "1.12.0")), feature = "1.12.1"))]
+fn test_fapl_set_file_locking() -> hdf5::Result<()> {
+    test_pl!(FA, file_locking: use_file_locking = false, ignore_when_disabled = false);
+    test_pl!(FA, file_locking: use_file_locking = false, ignore_when_disabled = true);
+    test_pl!(FA, file_locking: use_file_locking = true, ignore_when_disabled = true);
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "1.10.1")]
 fn test_fapl_set_mdc_image_config() -> hdf5::Result<()> {
@@ -873,6 +883,16 @@ fn test_dcpl_attr_creation_order() -> hdf5::Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "1.10.5")]
+fn test_dcpl_dset_no_attrs_hint() -> hdf5::Result<()> {
+    assert_eq!(DC::try_new()?.get_dset_no_attrs_hint()?, false);
+    assert_eq!(DC::try_new()?.dset_no_attrs_hint(), false);
+    test_pl!(DC, dset_no_attrs_hint: true);
+    test_pl!(DC, dset_no_attrs_hint: false);
+    Ok(())
+}
+
 type LC = LinkCreate;
 type LCB = LinkCreateBuilder;
 
@@ -932,3 +952,144 @@ fn test_lcpl_char_encoding() -> hdf5::Result<()> {
     assert_eq!(LCB::from_plist(&pl)?.finish()?.get_char_encoding()?, CharEncoding::Utf8);
     Ok(())
 }
+
+type DT = DatasetTransfer;
+type DTB = DatasetTransferBuilder;
+
+#[test]
+fn test_dxpl_common() -> hdf5::Result<()> {
+    test_pl_common!(DT, PropertyListClass::DataTransfer, |b: &mut DTB| b
+        .buffer_size(4 << 20)
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_buffer_size() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.get_buffer_size()?, 1 << 20);
+    test_pl!(DT, buffer_size: 4096);
+    test_pl!(DT, buffer_size: 16 << 20);
+    let pl = DTB::new().buffer_size(12345).finish()?;
+    assert_eq!(DTB::from_plist(&pl)?.finish()?.get_buffer_size()?, 12345);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_edc_check() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.get_edc_check()?, true);
+    test_pl!(DT, edc_check: false);
+    test_pl!(DT, edc_check: true);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_data_transform() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.get_data_transform()?, None);
+    test_pl!(DT, data_transform("x*2"): Some("x*2".to_owned()));
+    let pl = DTB::new().data_transform("x+1").finish()?;
+    assert_eq!(DTB::from_plist(&pl)?.finish()?.data_transform(), Some("x+1".to_owned()));
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_hyper_vector_size() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.get_hyper_vector_size()?, 1024);
+    test_pl!(DT, hyper_vector_size: 1);
+    test_pl!(DT, hyper_vector_size: 1 << 16);
+    let pl = DTB::new().hyper_vector_size(4096).finish()?;
+    assert_eq!(DTB::from_plist(&pl)?.finish()?.get_hyper_vector_size()?, 4096);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "have-parallel")]
+fn test_dxpl_io_mode() -> hdf5::Result<()> {
+    use hdf5::plist::dataset_transfer::{CollectiveOpt, IoMode};
+    assert_eq!(DT::try_new()?.get_io_mode()?, IoMode::Independent);
+    test_pl!(DT, io_mode: IoMode::Collective);
+    test_pl!(DT, io_mode: IoMode::Independent);
+    let pl = DTB::new().io_mode(IoMode::Collective).finish()?;
+    assert_eq!(DTB::from_plist(&pl)?.finish()?.get_io_mode()?, IoMode::Collective);
+    assert_eq!(DT::try_new()?.get_collective_opt()?, CollectiveOpt::CollectiveIo);
+    test_pl!(DT, collective_opt: CollectiveOpt::IndividualIo);
+    test_pl!(DT, collective_opt: CollectiveOpt::CollectiveIo);
+    let pl = DTB::new()
+        .io_mode(IoMode::Collective)
+        .collective_opt(CollectiveOpt::IndividualIo)
+        .finish()?;
+    assert_eq!(pl.get_io_mode()?, IoMode::Collective);
+    assert_eq!(pl.get_collective_opt()?, CollectiveOpt::IndividualIo);
+    let pl = DTB::from_plist(&pl)?.finish()?;
+    assert_eq!(pl.get_io_mode()?, IoMode::Collective);
+    assert_eq!(pl.get_collective_opt()?, CollectiveOpt::IndividualIo);
+    Ok(())
+}
+
+type LA = LinkAccess;
+type LAB = LinkAccessBuilder;
+
+#[test]
+fn test_lapl_common() -> hdf5::Result<()> {
+    test_pl_common!(LA, PropertyListClass::LinkAccess, |b: &mut LAB| b.nlinks(100).finish());
+    Ok(())
+}
+
+#[test]
+fn test_lapl_nlinks() -> hdf5::Result<()> {
+    assert_eq!(LA::try_new()?.get_nlinks()?, 16);
+    test_pl!(LA, nlinks: 1);
+    test_pl!(LA, nlinks: 1000);
+    let pl = LAB::new().nlinks(42).finish()?;
+    assert_eq!(LAB::from_plist(&pl)?.finish()?.get_nlinks()?, 42);
+    Ok(())
+}
+
+type GC = GroupCreate;
+type GCB = GroupCreateBuilder;
+
+#[test]
+fn test_gcpl_common() -> hdf5::Result<()> {
+    test_pl_common!(GC, PropertyListClass::GroupCreate, |b: &mut GCB| b
+        .est_link_info(100, 20)
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_est_link_info() -> hdf5::Result<()> {
+    assert_eq!(GC::try_new()?.get_est_link_info()?, EstLinkInfo::default());
+    test_pl!(GC, est_link_info: num_entries = 1000, name_len = 16);
+    test_pl!(GC, est_link_info: num_entries = 1, name_len = 100);
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_link_phase_change() -> hdf5::Result<()> {
+    assert_eq!(GC::try_new()?.get_link_phase_change()?, LinkPhaseChange::default());
+    test_pl!(GC, link_phase_change: max_compact = 16, min_dense = 12);
+    test_pl!(GC, link_phase_change: max_compact = 0, min_dense = 0);
+    let pl = GCB::new().link_phase_change(32, 4).finish()?;
+    assert_eq!(GCB::from_plist(&pl)?.finish()?.get_link_phase_change()?.max_compact, 32);
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_link_creation_order() -> hdf5::Result<()> {
+    assert_eq!(GC::try_new()?.get_link_creation_order()?.bits(), 0);
+    assert_eq!(GC::try_new()?.link_creation_order().bits(), 0);
+    test_pl!(GC, link_creation_order: LinkCreationOrder::TRACKED);
+    test_pl!(GC, link_creation_order: LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED);
+    assert!(GCB::new().link_creation_order(LinkCreationOrder::INDEXED).finish().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_obj_track_times() -> hdf5::Result<()> {
+    assert_eq!(GC::try_new()?.get_obj_track_times()?, true);
+    assert_eq!(GC::try_new()?.obj_track_times(), true);
+    test_pl!(GC, obj_track_times: true);
+    test_pl!(GC, obj_track_times: false);
+    let pl = GCB::new().obj_track_times(false).finish()?;
+    assert_eq!(GCB::from_plist(&pl)?.finish()?.get_obj_track_times()?, false);
+    Ok(())
+}
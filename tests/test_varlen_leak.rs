@@ -0,0 +1,56 @@
+//! Checks that reading variable-length data doesn't leak the buffers allocated by HDF5.
+//!
+//! This is a separate test binary so that no other tests allocate memory concurrently
+//! while the allocator statistics are being compared.
+
+mod common;
+
+use ndarray::{s, Array1};
+use rand::prelude::{SeedableRng, SmallRng};
+
+use self::common::gen::{Gen, VarLenStruct};
+use self::common::util::new_in_memory_file;
+
+/// Returns the number of bytes currently allocated via `malloc` (by any thread).
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn malloc_in_use() -> usize {
+    #[allow(deprecated)]
+    let info = unsafe { libc::mallinfo() };
+    info.uordblks as u32 as usize + info.hblkhd as u32 as usize
+}
+
+#[test]
+fn test_read_varlen_repeatedly() -> hdf5::Result<()> {
+    // Varlen buffers allocated by HDF5 during reads are owned (and freed) by the Rust-side
+    // values; reading the same dataset in a loop must neither leak nor double-free them.
+    let mut rng = SmallRng::seed_from_u64(42);
+    let file = new_in_memory_file()?;
+    let arr: Array1<VarLenStruct> = (0..1000).map(|_| VarLenStruct::gen(&mut rng)).collect();
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+    let attr_arr = arr.slice(s![..100]).to_owned();
+    let attr = ds.new_attr_builder().with_data(&attr_arr).create("a")?;
+    let read_all = || -> hdf5::Result<()> {
+        assert_eq!(ds.read_1d::<VarLenStruct>()?, arr);
+        assert_eq!(ds.read_slice_1d::<VarLenStruct, _>(100..200)?, arr.slice(s![100..200]));
+        assert_eq!(attr.read_1d::<VarLenStruct>()?, attr_arr);
+        Ok(())
+    };
+
+    // let the library caches and free lists settle first
+    for _ in 0..10 {
+        read_all()?;
+    }
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    let in_use = malloc_in_use();
+    for _ in 0..200 {
+        read_all()?;
+    }
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    {
+        // each read allocates at least one string per value, so leaking any of the buffers
+        // would grow the heap by several MiB (1200 values x 200 rounds x 32+ bytes)
+        let growth = malloc_in_use().saturating_sub(in_use);
+        assert!(growth < 1 << 20, "heap grew by {} bytes while reading varlen data", growth);
+    }
+    Ok(())
+}